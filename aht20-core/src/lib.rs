@@ -0,0 +1,248 @@
+//! Hardware independent part of the AHT20 driver
+//!
+//! Frame decoding, the CRC and the retry loop have no bus access, so they
+//! live in this crate and their tests run on the build machine:
+//!
+//! ```text
+//! cd aht20-core && cargo test
+//! ```
+//!
+//! Run it from this directory, `.cargo/config.toml` here switches the target
+//! back to the build machine.
+//!
+//! The driver in the main crate (`esp32_c3_super_mini_rust::aht20`) does the
+//! I2C transfers and re-exports what is public here.
+
+#![cfg_attr(not(test), no_std)]
+
+const STATUS_BUSY: u8 = 0x80; // Bit 7 of the status word
+const STATUS_CALIBRATED: u8 = 0x08; // Bit 3 of the status word
+
+#[derive(Clone, Copy, Debug)]
+pub struct Measurement {
+    /// °C
+    pub temperature: f32,
+    /// %RH
+    pub humidity: f32,
+}
+
+/// Why a measurement frame could not be decoded
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameError {
+    /// Measurement still running
+    Busy,
+    /// Values outside of the sensor's range, usually a corrupted transfer
+    InvalidData { temperature: f32, humidity: f32 },
+    /// CRC byte does not match the data, the transfer was cut short or garbled
+    Crc { expected: u8, received: u8 },
+}
+
+/// CRC-8 as used by the AHT20: polynomial 0x31, initial value 0xFF
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0xFFu8;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x31
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// True while a measurement is running
+pub fn is_busy(status: u8) -> bool {
+    status & STATUS_BUSY != 0
+}
+
+/// True once the calibration-enable bit of the status word is set
+pub fn is_calibrated(status: u8) -> bool {
+    status & STATUS_CALIBRATED != 0
+}
+
+/// Turns the 7 byte measurement frame (status, 5 data bytes, CRC) into a
+/// measurement. Fails with `Busy` while the measurement is still running.
+pub fn decode(frame: &[u8; 7]) -> Result<Measurement, FrameError> {
+    if is_busy(frame[0]) {
+        return Err(FrameError::Busy);
+    }
+
+    // The last byte is a CRC over the other six. A transfer that broke off
+    // part way leaves the remaining bytes at whatever the bus floated to,
+    // which the CRC catches instead of decoding garbage.
+    let expected = crc8(&frame[..6]);
+    if expected != frame[6] {
+        return Err(FrameError::Crc {
+            expected,
+            received: frame[6],
+        });
+    }
+
+    // Both values are 20-bit, MSB first (big-endian) and unsigned, the
+    // two share the middle nibble in frame[3]. Temperature sign comes
+    // only from the -50 offset, there is no two's complement involved.
+
+    // Process humidity data (20 bits) from frame[1], frame[2], and frame[3]
+    let humidity_raw =
+        ((frame[1] as u32) << 12) | ((frame[2] as u32) << 4) | ((frame[3] as u32) >> 4);
+    let humidity = (humidity_raw as f32) * 100.0 / 1048576.0;
+
+    // Process temperature data (20 bits) from frame[3], frame[4], and frame[5]
+    let temp_raw = ((frame[3] as u32 & 0x0F) << 16) | ((frame[4] as u32) << 8) | (frame[5] as u32);
+    let temperature = (temp_raw as f32) * 200.0 / 1048576.0 - 50.0;
+
+    // Validate data is in reasonable ranges
+    if !(0.0..=100.0).contains(&humidity) || !(-40.0..=85.0).contains(&temperature) {
+        return Err(FrameError::InvalidData {
+            temperature,
+            humidity,
+        });
+    }
+
+    Ok(Measurement {
+        temperature,
+        humidity,
+    })
+}
+
+/// Runs `attempt` until it succeeds, fails with an error `should_retry`
+/// turns down, or `retries` retries are used up. `should_retry` gets the
+/// error and the number of the retry it would start, counting from 1.
+pub fn retry<T, E>(
+    retries: u32,
+    mut attempt: impl FnMut() -> Result<T, E>,
+    mut should_retry: impl FnMut(&E, u32) -> bool,
+) -> Result<T, E> {
+    let mut retry = 0;
+    loop {
+        match attempt() {
+            Err(e) if retry < retries && should_retry(&e, retry + 1) => retry += 1,
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Measurement frame with calibrated status, 42.00 %RH and 23.74 °C
+    const FRAME: [u8; 7] = [0x1C, 0x6B, 0x85, 0x55, 0xE6, 0x2A, 0x37];
+
+    // FRAME with the transfer cut short after three bytes, the released bus
+    // reads as 0xFF for the rest
+    const SHORT_FRAME: [u8; 7] = [0x1C, 0x6B, 0x85, 0xFF, 0xFF, 0xFF, 0xFF];
+
+    fn is_crc(e: &FrameError) -> bool {
+        matches!(e, FrameError::Crc { .. })
+    }
+
+    #[test]
+    fn crc8_matches_datasheet_example() {
+        // Worked example for CRC-8 with polynomial 0x31 and init 0xFF, the
+        // same parameters as the AHT20 (Sensirion SHT3x datasheet, checksum
+        // calculation)
+        assert_eq!(crc8(&[0xBE, 0xEF]), 0x92);
+    }
+
+    #[test]
+    fn crc8_matches_check_value() {
+        // CRC-8/NRSC-5 check value over the ASCII digits 1 to 9
+        assert_eq!(crc8(b"123456789"), 0xF7);
+    }
+
+    #[test]
+    fn crc8_of_frame_and_crc_is_zero() {
+        assert_eq!(crc8(&FRAME), 0);
+    }
+
+    #[test]
+    fn decodes_valid_frame() {
+        let m = decode(&FRAME).unwrap();
+        assert!((m.humidity - 42.0).abs() < 0.01);
+        assert!((m.temperature - 23.74).abs() < 0.01);
+    }
+
+    #[test]
+    fn rejects_busy_frame() {
+        let mut frame = FRAME;
+        frame[0] |= STATUS_BUSY;
+        assert_eq!(decode(&frame).unwrap_err(), FrameError::Busy);
+    }
+
+    #[test]
+    fn rejects_bad_crc() {
+        let mut frame = FRAME;
+        frame[6] ^= 0x01;
+        assert_eq!(
+            decode(&frame).unwrap_err(),
+            FrameError::Crc {
+                expected: 0x37,
+                received: 0x36
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_short_read() {
+        assert!(is_crc(&decode(&SHORT_FRAME).unwrap_err()));
+    }
+
+    #[test]
+    fn retries_after_short_read() {
+        let frames = [SHORT_FRAME, FRAME];
+        let mut attempts = 0;
+        let m = retry(
+            2,
+            || {
+                attempts += 1;
+                decode(&frames[attempts - 1])
+            },
+            |e, _| is_crc(e),
+        )
+        .unwrap();
+        assert_eq!(attempts, 2);
+        assert!((m.humidity - 42.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn gives_up_after_retries() {
+        let mut attempts = 0;
+        let result = retry(
+            2,
+            || {
+                attempts += 1;
+                decode(&SHORT_FRAME)
+            },
+            |e, _| is_crc(e),
+        );
+        assert!(is_crc(&result.unwrap_err()));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn does_not_retry_busy() {
+        let mut frame = FRAME;
+        frame[0] |= STATUS_BUSY;
+        let mut attempts = 0;
+        let result = retry(
+            2,
+            || {
+                attempts += 1;
+                decode(&frame)
+            },
+            |e, _| is_crc(e),
+        );
+        assert_eq!(result.unwrap_err(), FrameError::Busy);
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn calibration_bit() {
+        assert!(is_calibrated(0x18));
+        assert!(!is_calibrated(0x10));
+    }
+}
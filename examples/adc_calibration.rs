@@ -0,0 +1,122 @@
+//! Converts raw ADC counts to engineering units with calibration curves
+//!
+//! The following wiring is assumed:
+//! - 3V3 => 10k => GPIO2 => 10k NTC thermistor (B=3950) => GND
+//!
+//! `LinearCal` covers sensors with a straight-line response (here counts to
+//! millivolts), `LookupTable` interpolates between measured points for
+//! non-linear ones like the NTC. Both implement `Calibration`, so the code
+//! using them does not care which one it got.
+//!
+//! The C3's SAR ADC is noisy, single reads jump by tens of counts. Averaging
+//! K reads cuts uncorrelated noise by sqrt(K): 4 reads halve it (+6 dB,
+//! about one bit), 16 reads quarter it (+12 dB), 64 reads bring it to an
+//! eighth (+18 dB). It only helps while the noise is at least about one
+//! count, which it always is here, and it costs K times the conversion time.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{adc, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    main,
+};
+use esp_println::println;
+
+const OVERSAMPLING: u32 = 64;
+
+trait Calibration {
+    fn apply(&self, raw: u16) -> f32;
+}
+
+/// `value = raw * slope + offset`
+struct LinearCal {
+    slope: f32,
+    offset: f32,
+}
+
+impl Calibration for LinearCal {
+    fn apply(&self, raw: u16) -> f32 {
+        raw as f32 * self.slope + self.offset
+    }
+}
+
+/// Points of `(raw, value)` sorted by raw. Values between points are
+/// linearly interpolated, outside the table they are clamped to the ends.
+struct LookupTable<const N: usize> {
+    points: [(u16, f32); N],
+}
+
+impl<const N: usize> Calibration for LookupTable<N> {
+    fn apply(&self, raw: u16) -> f32 {
+        let (first_raw, first_value) = self.points[0];
+        if raw <= first_raw {
+            return first_value;
+        }
+
+        for window in self.points.windows(2) {
+            let (raw0, value0) = window[0];
+            let (raw1, value1) = window[1];
+            if raw <= raw1 {
+                let t = (raw - raw0) as f32 / (raw1 - raw0) as f32;
+                return value0 + t * (value1 - value0);
+            }
+        }
+
+        self.points[N - 1].1
+    }
+}
+
+// 12-bit ADC with 11dB attenuation reads roughly 0..2500mV
+const COUNTS_TO_MV: LinearCal = LinearCal {
+    slope: 2500.0 / 4095.0,
+    offset: 0.0,
+};
+
+// NTC in the lower half of the divider, computed for B=3950, 10k @ 25°C.
+// The voltage drops as the temperature rises.
+const NTC_TABLE: LookupTable<7> = LookupTable {
+    points: [
+        (609, 80.0),
+        (1076, 60.0),
+        (1427, 50.0),
+        (1873, 40.0),
+        (2409, 30.0),
+        (3007, 20.0),
+        (3614, 10.0),
+    ],
+};
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO2, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let delay = Delay::new();
+
+    loop {
+        let mut read = || -> u16 { nb::block!(adc1.read_oneshot(&mut pin)).unwrap() };
+
+        let single = read();
+        let raw = adc::read_oversampled(&mut read, OVERSAMPLING);
+        let raw_14bit = adc::read_decimated(&mut read, 2);
+
+        println!(
+            "Single: {}, Oversampled: {}, 14-bit: {}, Voltage: {:.0} mV, Temperature: {:.1} °C",
+            single,
+            raw,
+            raw_14bit,
+            COUNTS_TO_MV.apply(raw),
+            NTC_TABLE.apply(raw)
+        );
+
+        delay.delay_millis(1000);
+    }
+}
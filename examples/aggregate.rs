@@ -0,0 +1,158 @@
+//! Samples an AHT20 every few seconds but only reports a per-minute summary
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! `Aggregator` keeps min, max, sum and count of everything fed into it.
+//! Once its window has elapsed, `window_elapsed` hands out the summary and
+//! starts the next window from scratch. Only the summary is printed, which
+//! cuts serial (or network) traffic by the number of samples per window
+//! while still showing the peaks a plain average would hide.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+};
+use esp_println::println;
+
+const SAMPLE_INTERVAL_MS: u32 = 5000;
+const WINDOW_MS: u64 = 60_000;
+
+#[derive(Debug)]
+struct Summary<T> {
+    min: T,
+    max: T,
+    avg: f32,
+    count: u32,
+}
+
+struct Aggregator<T> {
+    window: Duration,
+    start: Instant,
+    min: Option<T>,
+    max: Option<T>,
+    sum: f32,
+    count: u32,
+}
+
+impl<T: Copy + PartialOrd + Into<f32>> Aggregator<T> {
+    fn new(window_ms: u64) -> Self {
+        Aggregator {
+            window: Duration::from_millis(window_ms),
+            start: Instant::now(),
+            min: None,
+            max: None,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, sample: T) {
+        if self.min.is_none_or(|min| sample < min) {
+            self.min = Some(sample);
+        }
+        if self.max.is_none_or(|max| sample > max) {
+            self.max = Some(sample);
+        }
+        self.sum += sample.into();
+        self.count += 1;
+    }
+
+    /// Returns the summary and resets once the window is over. A window
+    /// without samples is skipped: it restarts but yields nothing.
+    fn window_elapsed(&mut self) -> Option<Summary<T>> {
+        if Instant::now() - self.start < self.window {
+            return None;
+        }
+
+        let summary = match (self.min, self.max) {
+            (Some(min), Some(max)) => Some(Summary {
+                min,
+                max,
+                avg: self.sum / self.count as f32,
+                count: self.count,
+            }),
+            _ => None,
+        };
+
+        self.start = Instant::now();
+        self.min = None;
+        self.max = None;
+        self.sum = 0.0;
+        self.count = 0;
+
+        summary
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    let mut temperature_agg = Aggregator::<f32>::new(WINDOW_MS);
+    let mut humidity_agg = Aggregator::<f32>::new(WINDOW_MS);
+
+    println!(
+        "Sampling every {} ms, reporting every {} s",
+        SAMPLE_INTERVAL_MS,
+        WINDOW_MS / 1000
+    );
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => {
+                temperature_agg.add(temperature);
+                humidity_agg.add(humidity);
+            }
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        if let Some(t) = temperature_agg.window_elapsed() {
+            println!(
+                "Temperature: min {:.2} °C, max {:.2} °C, avg {:.2} °C ({} samples)",
+                t.min, t.max, t.avg, t.count
+            );
+        }
+        if let Some(h) = humidity_agg.window_elapsed() {
+            println!(
+                "Humidity: min {:.2} %, max {:.2} %, avg {:.2} % ({} samples)",
+                h.min, h.max, h.avg, h.count
+            );
+        }
+
+        delay.delay_millis(SAMPLE_INTERVAL_MS);
+    }
+}
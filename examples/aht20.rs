@@ -3,134 +3,251 @@
 //! The following wiring is assumed:
 //! - SDA => GPIO8
 //! - SCL => GPIO9
+//!
+//! If the sensor does not answer at startup, the bus is scanned with SDA
+//! and SCL swapped to tell crossed wires from a missing sensor.
+//!
+//! The sensor can be unplugged and plugged back while running, after a few
+//! failed reads it is reported as disconnected and probed periodically.
+//!
+//! With `AVERAGE_SAMPLES` set, each reading is the mean of that many
+//! back-to-back measurements, reported with its 95% confidence interval as
+//! "23.41 ± 0.02 °C". The interval only covers the noise between samples,
+//! not the sensor's absolute accuracy (±0.3 °C, ±2 %RH per the datasheet).
 
 #![no_std]
 #![no_main]
 
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Aht20Error, Measurement},
+    config::CONFIG,
+    i2c_diag, print_banner,
+};
 use esp_backtrace as _;
 use esp_hal::{
     delay::Delay,
     i2c::master::{Config as I2cConfig, I2c},
     main,
-    time::Rate,
+    time::{self, Rate},
     Blocking,
 };
 use esp_println::println;
 
-// AHT20 constants
-const AHT20_ADDR: u8 = 0x38; // I2C address of AHT20
-const CMD_INIT: u8 = 0xBE; // Initialize command
-const CMD_MEASURE: u8 = 0xAC; // Trigger measurement command
-const INIT_PARAM1: u8 = 0x08;
-const INIT_PARAM2: u8 = 0x00;
-const MEASURE_PARAM1: u8 = 0x33;
-const MEASURE_PARAM2: u8 = 0x00;
+// Some AHT20 units only work reliably after an explicit reset on startup
+const RESET_ON_INIT: bool = false;
+
+// Disconnect handling
+const MAX_CONSECUTIVE_FAILURES: u32 = 3; // Failed reads before the sensor is considered gone
+const RECONNECT_INTERVAL_MS: u32 = 5000; // How often to look for the sensor while it is gone
+const MAX_STALENESS_MS: u64 = 10_000; // How long the last good reading may stand in for a failed one
+
+// Print every I2C transfer as `W 0x38 [AC 33 00]` / `R 0x38 -> [1C ...]`
+const TRACE_I2C: bool = false;
+
+// Take three readings and report the median to reject single-sample spikes
+const MEDIAN_OF_3: bool = false;
+
+// Average this many readings and report the mean with a 95% confidence
+// interval, 0 or 1 to disable
+const AVERAGE_SAMPLES: u32 = 0;
+
+// Units used for printing, readings are always stored in °C
+const UNITS: Units = Units::Metric;
+
+#[derive(Clone, Copy)]
+enum Units {
+    Metric,
+    Imperial,
+}
+
+impl Units {
+    fn temperature(self, celsius: f32) -> f32 {
+        match self {
+            Units::Metric => celsius,
+            Units::Imperial => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    fn temperature_symbol(self) -> &'static str {
+        match self {
+            Units::Metric => "°C",
+            Units::Imperial => "°F",
+        }
+    }
+}
+
+enum Value<T> {
+    Fresh(T),
+    Stale { value: T, age_ms: u64 },
+}
+
+/// Keeps the most recent successful reading, so a failed read can still
+/// report something (flagged as stale) until it gets older than `max_age_ms`.
+struct LastGood<T> {
+    last: Option<(T, u64)>,
+    max_age_ms: u64,
+}
+
+impl<T: Copy> LastGood<T> {
+    fn new(max_age_ms: u64) -> Self {
+        LastGood {
+            last: None,
+            max_age_ms,
+        }
+    }
+
+    fn update<E>(&mut self, result: Result<T, E>) -> Result<Value<T>, E> {
+        let now = time::Instant::now().duration_since_epoch().as_millis();
+        match result {
+            Ok(value) => {
+                self.last = Some((value, now));
+                Ok(Value::Fresh(value))
+            }
+            Err(e) => match self.last {
+                Some((value, taken_at)) if now - taken_at <= self.max_age_ms => Ok(Value::Stale {
+                    value,
+                    age_ms: now - taken_at,
+                }),
+                _ => Err(e),
+            },
+        }
+    }
+}
 
 #[main]
 fn main() -> ! {
-    let peripherals = esp_hal::init(esp_hal::Config::default());
-
-    // let mut led = Output::new(peripherals.GPIO8, Level::High, config);
+    let mut peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
 
     let delay = Delay::new();
 
-    let config = I2cConfig::default().with_frequency(Rate::from_khz(400));
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
     // Initialize I2C
-    let mut i2c: I2c<'_, Blocking> = match I2c::new(peripherals.I2C0, config) {
+    // Borrowed so the pins can be handed to the wiring check below
+    let mut i2c: I2c<'_, Blocking> = match I2c::new(&mut peripherals.I2C0, config) {
         Ok(i2c) => i2c,
         Err(e) => {
             panic!("Failed to initialize I2C: {:?}", e);
         }
     }
-    .with_sda(peripherals.GPIO8)
-    .with_scl(peripherals.GPIO9);
+    .with_sda(&mut peripherals.GPIO8)
+    .with_scl(&mut peripherals.GPIO9);
 
     // Initialize AHT20
-    match i2c.write(AHT20_ADDR, &[CMD_INIT, INIT_PARAM1, INIT_PARAM2]) {
-        Ok(_) => {
-            println!("AHT20 sensor initialized!");
-        }
+    let aht20 = Aht20::new(delay)
+        .with_trace(TRACE_I2C)
+        .with_reset_on_init(RESET_ON_INIT);
+    match aht20.start(&mut i2c) {
+        Ok(_) => println!("AHT20 sensor initialized!"),
         Err(e) => {
+            if let Aht20Error::I2c(_) = e {
+                drop(i2c);
+                i2c_diag::check_swapped(
+                    &mut peripherals.I2C0,
+                    &mut peripherals.GPIO8,
+                    &mut peripherals.GPIO9,
+                );
+            }
             panic!("Failed to initialize AHT20: {:?}", e);
         }
     }
-    delay.delay_millis(40);
+
+    let mut consecutive_failures = 0;
+    let mut connected = true;
+    let mut last_good = LastGood::new(MAX_STALENESS_MS);
 
     loop {
-        if i2c
-            .write(AHT20_ADDR, &[CMD_MEASURE, MEASURE_PARAM1, MEASURE_PARAM2])
-            .is_err()
-        {
-            println!("Failed to send measurement command to AHT20");
+        if !connected {
+            // Stay off the bus for a while, then check if the sensor is back
+            delay.delay_millis(RECONNECT_INTERVAL_MS);
+            match aht20.init(&mut i2c) {
+                Ok(_) => {
+                    println!("AHT20 reconnected");
+                    connected = true;
+                    consecutive_failures = 0;
+                }
+                Err(_) => {
+                    println!("AHT20 still disconnected");
+                    continue;
+                }
+            }
         }
 
-        // Wait for measurement to complete (at least 80ms)
-        delay.delay_millis(80);
-
-        // Read 7 bytes of data
-        let mut buffer = [0u8; 7];
-        if i2c.read(AHT20_ADDR, &mut buffer).is_err() {
-            println!("Failed to read data from AHT20");
+        let result = if AVERAGE_SAMPLES > 1 {
+            aht20.measure_averaged(&mut i2c, AVERAGE_SAMPLES).map(|avg| {
+                // Scaling the interval by the unit conversion factor, not
+                // converting it like a temperature
+                let scale = UNITS.temperature(1.0) - UNITS.temperature(0.0);
+                println!(
+                    "Averaged over {}: {:.2} ± {:.2} {} (SE {:.3}), {:.2} ± {:.2} % (SE {:.3}), 95% CI",
+                    avg.samples,
+                    UNITS.temperature(avg.temperature.mean),
+                    avg.temperature.ci95 * scale,
+                    UNITS.temperature_symbol(),
+                    avg.temperature.std_error * scale,
+                    avg.humidity.mean,
+                    avg.humidity.ci95,
+                    avg.humidity.std_error
+                );
+                Measurement {
+                    temperature: avg.temperature.mean,
+                    humidity: avg.humidity.mean,
+                }
+            })
+        } else if MEDIAN_OF_3 {
+            aht20.read_median3(&mut i2c)
+        } else {
+            aht20.read(&mut i2c)
+        };
+        if let Err(e) = &result {
+            println!("Failed to read data from AHT20: {:?}", e);
+            consecutive_failures += 1;
+        } else {
+            consecutive_failures = 0;
         }
 
-        // Check status bit for calibration
-        if (buffer[0] & 0x08) == 0 {
-            println!("AHT20 sensor is not calibrated!");
-            println!("Resetting AHT20 sensor...");
-
-            // Soft reset command
-            i2c.write(AHT20_ADDR, &[0xBA]);
-            delay.delay_millis(80);
-
-            println!("Initializing AHT20 sensor...");
-            i2c.write(AHT20_ADDR, &[CMD_INIT, INIT_PARAM1, INIT_PARAM2]);
-
-            // Wait for calibration to complete - at least 10ms recommended
-            delay.delay_millis(500);
-
-            // Check if calibration was successful
-            let mut status = [0u8];
-            i2c.write_read(AHT20_ADDR, &[0x71], &mut status);
-
-            if (status[0] & 0x08) == 0 {
+        match last_good.update(result) {
+            Ok(Value::Fresh(Measurement {
+                temperature,
+                humidity,
+            })) => {
                 println!(
-                    "Calibration still not successful. Status: {:02x}",
-                    status[0]
+                    "Temperature: {:.2} {}, Humidity: {:.2} %",
+                    UNITS.temperature(temperature),
+                    UNITS.temperature_symbol(),
+                    humidity
                 );
-            } else {
-                println!("Calibration successful!");
+            }
+            Ok(Value::Stale {
+                value:
+                    Measurement {
+                        temperature,
+                        humidity,
+                    },
+                age_ms,
+            }) => {
+                println!(
+                    "Temperature: {:.2} {}, Humidity: {:.2} % (stale, {} ms old)",
+                    UNITS.temperature(temperature),
+                    UNITS.temperature_symbol(),
+                    humidity,
+                    age_ms
+                );
+            }
+            Err(_) => {
+                println!("No valid reading in the last {} ms", MAX_STALENESS_MS);
             }
         }
 
-        // Check if device is busy
-        if (buffer[0] & 0x80) != 0 {
-            println!("AHT20 sensor is busy!");
-        }
-
-        // Process humidity data (20 bits) from buffer[1], buffer[2], and buffer[3]
-        let humidity_raw =
-            ((buffer[1] as u32) << 12) | ((buffer[2] as u32) << 4) | ((buffer[3] as u32) >> 4);
-        let humidity = (humidity_raw as f32) * 100.0 / 1048576.0;
-
-        // Process temperature data (20 bits) from buffer[3], buffer[4], and buffer[5]
-        let temp_raw =
-            ((buffer[3] as u32 & 0x0F) << 16) | ((buffer[4] as u32) << 8) | (buffer[5] as u32);
-        let temperature = (temp_raw as f32) * 200.0 / 1048576.0 - 50.0;
-
-        // Validate data is in reasonable ranges
-        if !(0.0..=100.0).contains(&humidity) || !(-40.0..=85.0).contains(&temperature) {
-            println!(
-                "Invalid data from AHT20: temperature = {}, humidity = {}",
-                temperature, humidity
-            )
-        } else {
+        if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
             println!(
-                "Temperature: {:.2} °C, Humidity: {:.2} %",
-                temperature, humidity
+                "AHT20 disconnected after {} failed reads, retrying every {} ms",
+                consecutive_failures, RECONNECT_INTERVAL_MS
             );
+            connected = false;
+            continue;
         }
 
-        // Wait 2 seconds between readings
-        delay.delay_millis(2000);
+        delay.delay_millis(CONFIG.sample_interval_ms);
     }
 }
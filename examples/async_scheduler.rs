@@ -0,0 +1,102 @@
+//! Samples an AHT20 on a fixed cadence with an embassy `Ticker`
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! `Timer::after(2s)` at the end of each iteration waits 2 s *after* the
+//! work, so every iteration takes 2 s plus however long the read took and
+//! the schedule drifts further behind with every sample. A `Ticker` instead
+//! fires at fixed points in time (start + n * 2 s), whatever happened in
+//! between, so the period holds and the error does not add up.
+//!
+//! To make this visible the read is followed by a made-up extra processing
+//! delay that varies from iteration to iteration. The printed interval stays
+//! at 2000 ms anyway, and the drift against the ideal schedule stays near 0.
+//! `Timer::after` would have drifted by the sum of all read times instead.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Ticker, Timer};
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Aht20Error, Measurement, MEASUREMENT_TIME_MS},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    time::Rate,
+    timer::timg::TimerGroup,
+    Blocking,
+};
+use esp_println::println;
+
+const PERIOD: Duration = Duration::from_secs(2);
+
+/// Waits for the measurement on the timer instead of blocking the executor
+async fn read_aht20(aht20: &Aht20, i2c: &mut I2c<'_, Blocking>) -> Result<Measurement, Aht20Error> {
+    aht20.trigger(i2c)?;
+    Timer::after(Duration::from_millis(MEASUREMENT_TIME_MS as u64)).await;
+    aht20.fetch(i2c)
+}
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(Delay::new());
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    let start = Instant::now();
+    let mut last = start;
+    let mut samples = 0u64;
+    let mut ticker = Ticker::every(PERIOD);
+
+    loop {
+        let now = Instant::now();
+        let ideal = start + PERIOD * samples as u32;
+        println!(
+            "Interval: {} ms, drift from schedule: {} us",
+            (now - last).as_millis(),
+            now.as_micros() as i64 - ideal.as_micros() as i64
+        );
+        last = now;
+        samples += 1;
+
+        match read_aht20(&aht20, &mut i2c).await {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => println!(
+                "Temperature: {:.2} °C, Humidity: {:.2} %",
+                temperature, humidity
+            ),
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        // Stand-in for processing that takes a different time every round
+        let extra = Duration::from_millis(samples * 37 % 300);
+        Timer::after(extra).await;
+
+        ticker.next().await;
+    }
+}
@@ -0,0 +1,131 @@
+//! Scales a sk6812 RGBW LED strip's brightness with the ambient light
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//! - BH1750 SDA => GPIO8
+//! - BH1750 SCL => GPIO9
+//!
+//! The BH1750 is read once a second. The lux value maps linearly onto a
+//! target brightness between `MIN_BRIGHTNESS` (dark room) and
+//! `MAX_BRIGHTNESS` (at `FULL_BRIGHTNESS_LUX` and above). The strip does not
+//! jump to the target: every frame it moves a fraction of the way there, so
+//! a hand passing over the sensor or a flickering lamp does not make the
+//! strip flicker too.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Timer};
+use esp32_c3_super_mini_rust::{
+    bh1750::{self, Bh1750},
+    config::CONFIG,
+    print_banner, sk6812,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+const FRAME_TIME: Duration = Duration::from_millis(20);
+
+const MEASUREMENT_TIME: Duration = Duration::from_millis(bh1750::MEASUREMENT_TIME_MS as u64);
+const LIGHT_INTERVAL: Duration = Duration::from_secs(1);
+
+const MIN_BRIGHTNESS: f32 = 2.0;
+const MAX_BRIGHTNESS: f32 = 255.0;
+const FULL_BRIGHTNESS_LUX: f32 = 500.0;
+// Fraction of the remaining distance to the target covered per frame
+const SMOOTHING: f32 = 0.05;
+
+// Color at full brightness, scaled down by the current brightness
+const BASE_COLOR: (u8, u8, u8, u8) = (255, 120, 40, 60);
+
+fn target_brightness(lux: f32) -> f32 {
+    let fraction = (lux / FULL_BRIGHTNESS_LUX).clamp(0.0, 1.0);
+    MIN_BRIGHTNESS + (MAX_BRIGHTNESS - MIN_BRIGHTNESS) * fraction
+}
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let bh1750 = Bh1750::new(Delay::new());
+    if let Err(e) = bh1750.start(&mut i2c) {
+        panic!("Failed to initialize BH1750: {:?}", e);
+    }
+
+    let mut brightness = MIN_BRIGHTNESS;
+    let mut target = MIN_BRIGHTNESS;
+    let mut next_light = Instant::now();
+    // Set while a measurement is running, so the strip keeps animating
+    // instead of waiting for the sensor
+    let mut result_at: Option<Instant> = None;
+
+    loop {
+        let now = Instant::now();
+        match result_at {
+            None if now >= next_light => match bh1750.trigger(&mut i2c) {
+                Ok(_) => result_at = Some(now + MEASUREMENT_TIME),
+                Err(e) => {
+                    println!("Failed to start BH1750 measurement: {:?}", e);
+                    next_light = now + LIGHT_INTERVAL;
+                }
+            },
+            Some(at) if now >= at => {
+                result_at = None;
+                next_light = now + LIGHT_INTERVAL;
+                match bh1750.fetch(&mut i2c) {
+                    Ok(lux) => {
+                        target = target_brightness(lux);
+                        println!(
+                            "Ambient: {:.1} lx, target brightness: {:.0}, current: {:.0}",
+                            lux, target, brightness
+                        );
+                    }
+                    Err(e) => println!("Failed to read data from BH1750: {:?}", e),
+                }
+            }
+            _ => {}
+        }
+
+        brightness += (target - brightness) * SMOOTHING;
+
+        let pixel = sk6812::scale(BASE_COLOR, brightness as u8);
+        strip.flush(&[pixel; NUM_LEDS]).await;
+
+        Timer::after(FRAME_TIME).await;
+    }
+}
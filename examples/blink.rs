@@ -6,6 +6,7 @@
 #![no_std]
 #![no_main]
 
+use esp32_c3_super_mini_rust::print_banner;
 use esp_backtrace as _;
 use esp_hal::{
     delay::Delay,
@@ -17,6 +18,7 @@ use esp_println::println;
 #[main]
 fn main() -> ! {
     let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
 
     let config = OutputConfig::default();
     let mut led = Output::new(peripherals.GPIO8, Level::High, config);
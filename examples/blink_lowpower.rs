@@ -0,0 +1,74 @@
+//! Blinks an LED at 1 Hz, sleeping instead of busy-waiting in between
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8 (the onboard LED, lit when the pin is low)
+//!
+//! `delay_millis` spins the CPU at full speed for the whole wait, so the
+//! plain blink example draws about as much while "doing nothing" as while
+//! working: roughly 20-25 mA on this board. Light sleep stops the CPU clock
+//! and gates most of the chip, a timer in the RTC domain wakes it up again
+//! and execution continues right after the call, with RAM and GPIO levels
+//! kept. That cuts the draw between blinks to well under 1 mA, plus
+//! whatever the LED and the power LED take.
+//!
+//! Set `USE_LIGHT_SLEEP` to false to compare both on a USB power meter or
+//! a multimeter in series with the supply. With the board on the USB port
+//! the serial console may drop out while the chip sleeps, power it from
+//! 5V/3V3 for the measurement.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+    rtc_cntl::{sleep::TimerWakeupSource, Rtc},
+};
+use esp_println::println;
+
+const USE_LIGHT_SLEEP: bool = true;
+
+const ON_MS: u64 = 50;
+const OFF_MS: u64 = 950;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = OutputConfig::default();
+    let mut led = Output::new(peripherals.GPIO8, Level::High, config);
+
+    let mut rtc = Rtc::new(peripherals.LPWR);
+    let delay = Delay::new();
+
+    // Either wait style keeps the same 1 Hz rhythm
+    let mut wait = |ms: u64| {
+        if USE_LIGHT_SLEEP {
+            let timer = TimerWakeupSource::new(core::time::Duration::from_millis(ms));
+            rtc.sleep_light(&[&timer]);
+        } else {
+            delay.delay_millis(ms as u32);
+        }
+    };
+
+    println!(
+        "Blinking with {}",
+        if USE_LIGHT_SLEEP {
+            "light sleep"
+        } else {
+            "busy-wait delays"
+        }
+    );
+
+    loop {
+        // A short flash keeps the LED's own share of the current low too
+        led.set_low();
+        wait(ON_MS);
+        led.set_high();
+        wait(OFF_MS);
+    }
+}
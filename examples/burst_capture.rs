@@ -0,0 +1,86 @@
+//! Captures a burst of ADC samples at full speed and dumps them as CSV
+//!
+//! The following wiring is assumed:
+//! - Analog signal (0..2.5V) => GPIO3
+//! - Button => GPIO0 -> GND
+//!
+//! Printing every sample would limit the sample rate to what the serial port
+//! can carry. Here a button press fills a RAM buffer as fast as the ADC goes
+//! and only then prints it, so capture speed and serial speed do not depend
+//! on each other. Presses during the capture or the dump are ignored.
+//!
+//! Output between `# capture start` and `# capture end` is CSV with a header
+//! line. Copy it into a file, or filter the serial log with a script, and
+//! load it in a spreadsheet or Python.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    gpio::{Input, InputConfig, Pull},
+    main,
+    time::Instant,
+};
+use esp_println::println;
+
+const CAPTURE_LEN: usize = 2000;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let button = Input::new(peripherals.GPIO0, in_config);
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let delay = Delay::new();
+
+    // Sample values and their time since the capture started
+    let mut values = [0u16; CAPTURE_LEN];
+    let mut times_us = [0u32; CAPTURE_LEN];
+    let mut captures = 0u32;
+
+    println!("Press the button to capture {} samples", CAPTURE_LEN);
+
+    loop {
+        if button.is_high() {
+            delay.delay_millis(10);
+            continue;
+        }
+
+        let start = Instant::now();
+        for (value, time_us) in values.iter_mut().zip(times_us.iter_mut()) {
+            *value = nb::block!(adc1.read_oneshot(&mut pin)).unwrap();
+            *time_us = (Instant::now() - start).as_micros() as u32;
+        }
+        let elapsed_us = times_us[CAPTURE_LEN - 1].max(1);
+        captures += 1;
+
+        println!("# capture start {}", captures);
+        println!(
+            "# {} samples in {} us, {} samples/s",
+            CAPTURE_LEN,
+            elapsed_us,
+            CAPTURE_LEN as u64 * 1_000_000 / elapsed_us as u64
+        );
+        println!("index,time_us,raw");
+        for (i, (value, time_us)) in values.iter().zip(times_us.iter()).enumerate() {
+            println!("{},{},{}", i, time_us, value);
+        }
+        println!("# capture end {}", captures);
+
+        // Wait for the release so one long press is one capture
+        while button.is_low() {
+            delay.delay_millis(10);
+        }
+        delay.delay_millis(50);
+    }
+}
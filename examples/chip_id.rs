@@ -0,0 +1,54 @@
+//! Reads the factory MAC address from eFuse and derives a unique device name
+//!
+//! No wiring needed.
+//!
+//! The base MAC is burned into eFuse at the factory and is unique per chip,
+//! which makes it a good source for MQTT client IDs, hostnames or log tags.
+//! The Wi-Fi station uses this MAC, the other interfaces derive theirs
+//! from it by adding a small offset to the last byte.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{delay::Delay, efuse::Efuse, main};
+use esp_println::println;
+
+const NAME_PREFIX: &[u8] = b"c3-";
+const NAME_LEN: usize = NAME_PREFIX.len() + 12;
+
+/// Writes `c3-` followed by the MAC as 12 lowercase hex digits into `buf`
+fn chip_id_str(buf: &mut [u8; NAME_LEN]) -> &str {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    buf[..NAME_PREFIX.len()].copy_from_slice(NAME_PREFIX);
+    for (i, byte) in Efuse::read_base_mac_address().iter().enumerate() {
+        let pos = NAME_PREFIX.len() + i * 2;
+        buf[pos] = HEX[(byte >> 4) as usize];
+        buf[pos + 1] = HEX[(byte & 0x0F) as usize];
+    }
+
+    // Only ASCII was written
+    core::str::from_utf8(buf).unwrap()
+}
+
+#[main]
+fn main() -> ! {
+    let _peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mac = Efuse::read_base_mac_address();
+    println!(
+        "Base MAC: {:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]
+    );
+
+    let mut buf = [0u8; NAME_LEN];
+    println!("Device name: {}", chip_id_str(&mut buf));
+
+    let delay = Delay::new();
+    loop {
+        delay.delay_millis(1000);
+    }
+}
@@ -0,0 +1,133 @@
+//! Keeps the last log lines in RTC RAM so they can be read after a reset
+//!
+//! No wiring needed.
+//!
+//! `log!` prints like `println!` and also appends the line to a ring buffer
+//! in RTC fast memory. That memory is not cleared by a warm reset (panic,
+//! watchdog, software reset), only by a power cycle, so on the next boot the
+//! lines written right before the crash are still there and get printed.
+//!
+//! The demo logs a counter while feeding the RTC watchdog, then panics. The
+//! panic handler prints its backtrace and halts, the watchdog stops being
+//! fed and resets the chip, and the new boot dumps the pre-reset log.
+//!
+//! A magic value marks the buffer as valid, after a power cycle the RTC RAM
+//! holds random data and the magic does not match.
+
+#![no_std]
+#![no_main]
+
+use core::{fmt::Write, ptr::addr_of_mut};
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main, ram,
+    rtc_cntl::{reset_reason, Rtc, RwdtStage},
+    system::Cpu,
+    time::Duration,
+};
+use esp_println::{print, println};
+
+const LOG_SIZE: usize = 1024;
+const LOG_MAGIC: u32 = 0x10C5_10C5;
+
+const WATCHDOG_TIMEOUT_MS: u64 = 3000;
+const PANIC_AFTER: u32 = 10;
+
+#[ram(rtc_fast, persistent)]
+static mut LOG_VALID: u32 = 0;
+#[ram(rtc_fast, persistent)]
+static mut LOG_HEAD: u32 = 0;
+#[ram(rtc_fast, persistent)]
+static mut LOG_LEN: u32 = 0;
+#[ram(rtc_fast, persistent)]
+static mut LOG_BUF: [u8; LOG_SIZE] = [0; LOG_SIZE];
+
+/// Writer appending to the persistent ring, the oldest bytes are overwritten
+/// once it is full. Only use it inside a critical section.
+struct RingLog;
+
+impl Write for RingLog {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        unsafe {
+            let head = addr_of_mut!(LOG_HEAD);
+            let len = addr_of_mut!(LOG_LEN);
+            for byte in s.bytes() {
+                (*addr_of_mut!(LOG_BUF))[*head as usize] = byte;
+                *head = (*head + 1) % LOG_SIZE as u32;
+                if (*len as usize) < LOG_SIZE {
+                    *len += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! log {
+    ($($arg:tt)*) => {{
+        println!($($arg)*);
+        critical_section::with(|_| {
+            writeln!(RingLog, $($arg)*).ok();
+        });
+    }};
+}
+
+/// Prints whatever the previous boot logged, if the buffer is valid, and
+/// starts a fresh log either way.
+fn recover_crash_log() {
+    critical_section::with(|_| unsafe {
+        if *addr_of_mut!(LOG_VALID) == LOG_MAGIC {
+            let head = *addr_of_mut!(LOG_HEAD) as usize;
+            let len = (*addr_of_mut!(LOG_LEN) as usize).min(LOG_SIZE);
+            let buf = &*addr_of_mut!(LOG_BUF);
+            let start = (head + LOG_SIZE - len) % LOG_SIZE;
+
+            println!("--- log from before the reset ({} bytes) ---", len);
+            for i in 0..len {
+                print!("{}", buf[(start + i) % LOG_SIZE] as char);
+            }
+            println!("--- end of previous log ---");
+        } else {
+            println!("No previous log (power-on or first boot)");
+        }
+
+        *addr_of_mut!(LOG_HEAD) = 0;
+        *addr_of_mut!(LOG_LEN) = 0;
+        *addr_of_mut!(LOG_VALID) = LOG_MAGIC;
+    });
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    println!("Reset reason: {:?}", reset_reason(Cpu::ProCpu));
+    recover_crash_log();
+
+    let mut rtc = Rtc::new(peripherals.LPWR);
+    rtc.rwdt.set_timeout(
+        RwdtStage::Stage0,
+        Duration::from_millis(WATCHDOG_TIMEOUT_MS),
+    );
+    rtc.rwdt.enable();
+
+    let delay = Delay::new();
+
+    log!("Booted, panicking after {} ticks", PANIC_AFTER);
+
+    for tick in 1..=PANIC_AFTER {
+        log!("tick {}", tick);
+        rtc.rwdt.feed();
+        delay.delay_millis(500);
+    }
+
+    log!(
+        "About to panic, the watchdog resets in {} ms",
+        WATCHDOG_TIMEOUT_MS
+    );
+    panic!("Deliberate panic to demonstrate the crash log");
+}
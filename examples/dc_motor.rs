@@ -0,0 +1,142 @@
+//! Drives a small DC motor through an H-bridge with speed and direction
+//!
+//! The following wiring is assumed:
+//! - H-bridge IN1 => GPIO3
+//! - H-bridge IN2 => GPIO4
+//! - H-bridge motor outputs (OUT1/OUT2) => motor
+//! - H-bridge motor supply => separate battery or supply, GND shared with the board
+//!
+//! Works with a DRV8833 (AIN1/AIN2, AOUT1/AOUT2, VM up to 10.8V) or an
+//! L298N (IN1/IN2, OUT1/OUT2, keep the ENA jumper on so the channel is
+//! always enabled). Never power the motor from the board's 3V3 pin, the
+//! start-up current alone is enough to brown it out.
+//!
+//! `set_speed` takes -100..=100: the sign picks which input gets the PWM,
+//! the magnitude sets the duty. Below about a third of full duty a small
+//! motor just hums without turning, so non-zero speeds are mapped onto
+//! `MIN_DUTY_PCT..=100` instead of 0..=100. Stopping can either coast (both
+//! inputs low, the motor spins down freely) or brake (both inputs high, the
+//! bridge shorts the motor and it stops quickly).
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    ledc::{
+        channel::{self, Channel, ChannelIFace},
+        timer::{self, TimerIFace},
+        LSGlobalClkSource, Ledc, LowSpeed,
+    },
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+// Lowest duty that still gets the motor turning, tune for your motor
+const MIN_DUTY_PCT: u8 = 30;
+// Above the audible range, so the motor does not whine
+const PWM_FREQUENCY_KHZ: u32 = 20;
+
+struct DcMotor<'a> {
+    in1: Channel<'a, LowSpeed>,
+    in2: Channel<'a, LowSpeed>,
+}
+
+impl<'a> DcMotor<'a> {
+    fn new(in1: Channel<'a, LowSpeed>, in2: Channel<'a, LowSpeed>) -> Self {
+        let mut motor = DcMotor { in1, in2 };
+        motor.coast();
+        motor
+    }
+
+    fn set_inputs(&mut self, in1_pct: u8, in2_pct: u8) {
+        self.in1.set_duty(in1_pct).unwrap();
+        self.in2.set_duty(in2_pct).unwrap();
+    }
+
+    /// -100..=100, positive is forward. Zero coasts.
+    fn set_speed(&mut self, speed: i16) {
+        let speed = speed.clamp(-100, 100);
+        if speed == 0 {
+            self.coast();
+            return;
+        }
+
+        // Map 1..=100 onto MIN_DUTY_PCT..=100 so small speeds still turn
+        let magnitude = speed.unsigned_abs() as u32;
+        let duty = (MIN_DUTY_PCT as u32 + (100 - MIN_DUTY_PCT as u32) * magnitude / 100) as u8;
+
+        if speed > 0 {
+            self.set_inputs(duty, 0);
+        } else {
+            self.set_inputs(0, duty);
+        }
+    }
+
+    /// Both inputs low, the motor is disconnected and spins down on its own
+    fn coast(&mut self) {
+        self.set_inputs(0, 0);
+    }
+
+    /// Both inputs high, the motor terminals are shorted and it stops fast
+    fn brake(&mut self) {
+        self.set_inputs(100, 100);
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: Rate::from_khz(PWM_FREQUENCY_KHZ),
+        })
+        .unwrap();
+
+    let channel_config = channel::config::Config {
+        timer: &lstimer0,
+        duty_pct: 0,
+        pin_config: channel::config::PinConfig::PushPull,
+    };
+    let mut in1 = ledc.channel(channel::Number::Channel0, peripherals.GPIO3);
+    in1.configure(channel_config).unwrap();
+    let mut in2 = ledc.channel(channel::Number::Channel1, peripherals.GPIO4);
+    in2.configure(channel_config).unwrap();
+
+    let mut motor = DcMotor::new(in1, in2);
+    let delay = Delay::new();
+
+    loop {
+        println!("Forward, ramping up");
+        for speed in (0..=100).step_by(10) {
+            motor.set_speed(speed);
+            delay.delay_millis(200);
+        }
+        delay.delay_millis(1000);
+
+        println!("Coast");
+        motor.coast();
+        delay.delay_millis(2000);
+
+        println!("Reverse, ramping up");
+        for speed in (0..=100).step_by(10) {
+            motor.set_speed(-speed);
+            delay.delay_millis(200);
+        }
+        delay.delay_millis(1000);
+
+        println!("Brake");
+        motor.brake();
+        delay.delay_millis(2000);
+    }
+}
@@ -0,0 +1,172 @@
+//! Reads a DHT22 (AM2302) or DHT11 over its single-wire protocol
+//!
+//! The following wiring is assumed:
+//! - DHT data => GPIO3, with a 10k pull-up to 3V3 (most breakout boards have one)
+//! - DHT VCC => 3V3, GND => GND
+//!
+//! The pin runs as open drain: writing high releases the line to the
+//! pull-up, so the same pin can pull the line low for the start pulse and
+//! then read what the sensor sends without switching direction.
+//!
+//! A read goes like this:
+//! 1. host pulls the line low for at least 1 ms (DHT11: 18 ms), then releases it
+//! 2. sensor answers with 80 us low, 80 us high
+//! 3. 40 bits follow, each a 50 us low followed by a high pulse: 26-28 us
+//!    for a 0, 70 us for a 1
+//! 4. the fifth byte is the sum of the first four, as a checksum
+//!
+//! Only the high pulse widths matter, so the code times those and compares
+//! them to `BIT_THRESHOLD_US`. An interrupt in the middle of a read stretches
+//! the measured pulse and flips bits, so the whole transfer (about 5 ms)
+//! runs inside a critical section.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Flex, Pull},
+    main,
+    time::Instant,
+};
+use esp_println::println;
+
+const MODEL: Model = Model::Dht22;
+
+// High pulses longer than this are a 1
+const BIT_THRESHOLD_US: u64 = 48;
+// No phase of the protocol lasts longer than this
+const LEVEL_TIMEOUT_US: u64 = 200;
+
+#[derive(Clone, Copy)]
+enum Model {
+    Dht11,
+    Dht22,
+}
+
+impl Model {
+    fn start_pulse_us(self) -> u32 {
+        match self {
+            Model::Dht11 => 18_000,
+            Model::Dht22 => 1_100,
+        }
+    }
+
+    /// Shortest interval between reads, faster reads return stale data
+    fn min_interval_ms(self) -> u32 {
+        match self {
+            Model::Dht11 => 1000,
+            Model::Dht22 => 2000,
+        }
+    }
+
+    /// (temperature °C, humidity %) from the four data bytes
+    fn decode(self, data: &[u8; 5]) -> (f32, f32) {
+        match self {
+            Model::Dht11 => (
+                data[2] as f32 + data[3] as f32 / 10.0,
+                data[0] as f32 + data[1] as f32 / 10.0,
+            ),
+            Model::Dht22 => {
+                let humidity = u16::from_be_bytes([data[0], data[1]]) as f32 / 10.0;
+                // Sign and magnitude, not two's complement
+                let magnitude = u16::from_be_bytes([data[2] & 0x7F, data[3]]) as f32 / 10.0;
+                let temperature = if data[2] & 0x80 != 0 {
+                    -magnitude
+                } else {
+                    magnitude
+                };
+                (temperature, humidity)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum DhtError {
+    /// The sensor did not answer the start pulse
+    NoResponse,
+    /// The line stopped changing part way through, at bit `bit`
+    Timeout {
+        bit: usize,
+    },
+    Checksum {
+        expected: u8,
+        received: u8,
+    },
+}
+
+/// Waits while the line is at `high`, returns how long that took in us
+fn wait_while(pin: &Flex, high: bool) -> Option<u64> {
+    let start = Instant::now();
+    while pin.is_high() == high {
+        let elapsed = (Instant::now() - start).as_micros();
+        if elapsed > LEVEL_TIMEOUT_US {
+            return None;
+        }
+    }
+    Some((Instant::now() - start).as_micros())
+}
+
+fn read_dht(pin: &mut Flex, delay: &Delay) -> Result<(f32, f32), DhtError> {
+    pin.set_low();
+    delay.delay_micros(MODEL.start_pulse_us());
+
+    let data = critical_section::with(|_| {
+        pin.set_high();
+
+        // The sensor takes 20-40 us to pull low, then 80 us low, 80 us high
+        wait_while(pin, true).ok_or(DhtError::NoResponse)?;
+        wait_while(pin, false).ok_or(DhtError::NoResponse)?;
+        wait_while(pin, true).ok_or(DhtError::NoResponse)?;
+
+        let mut data = [0u8; 5];
+        for bit in 0..40 {
+            wait_while(pin, false).ok_or(DhtError::Timeout { bit })?;
+            let high_us = wait_while(pin, true).ok_or(DhtError::Timeout { bit })?;
+            if high_us > BIT_THRESHOLD_US {
+                data[bit / 8] |= 0x80 >> (bit % 8);
+            }
+        }
+        Ok(data)
+    })?;
+
+    let expected = data[..4].iter().fold(0u8, |sum, &b| sum.wrapping_add(b));
+    if expected != data[4] {
+        return Err(DhtError::Checksum {
+            expected,
+            received: data[4],
+        });
+    }
+
+    Ok(MODEL.decode(&data))
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut pin = Flex::new(peripherals.GPIO3);
+    pin.set_as_open_drain(Pull::Up);
+    pin.set_high();
+
+    let delay = Delay::new();
+
+    // The sensor ignores requests for a second after power-up
+    delay.delay_millis(1000);
+
+    loop {
+        match read_dht(&mut pin, &delay) {
+            Ok((temperature, humidity)) => println!(
+                "Temperature: {:.1} °C, Humidity: {:.1} %",
+                temperature, humidity
+            ),
+            Err(e) => println!("Failed to read data from DHT: {:?}", e),
+        }
+
+        delay.delay_millis(MODEL.min_interval_ms());
+    }
+}
@@ -0,0 +1,91 @@
+//! Drives two independent sk6812 RGBW LED strips from two RMT channels
+//!
+//! The following wiring is assumed:
+//! - first led_strip_data => GPIO4
+//! - second led_strip_data => GPIO5
+//!
+//! The C3 RMT has four channels: `channel0` and `channel1` can transmit,
+//! `channel2` and `channel3` can only receive. So two strips is the most
+//! the RMT can drive at once, each on its own channel and pin.
+//!
+//! The first strip runs a fast chase and is flushed every frame, the second
+//! steps through colors and is only flushed every `SLOW_EVERY` frames. The
+//! second strip keeps showing its last frame in between: a strip only
+//! listens to its own data line, so flushing one never disturbs the other.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+
+const FRAME_TIME: Duration = Duration::from_millis(50);
+const SLOW_EVERY: u32 = 10;
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel0 = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let channel1 = rmt
+        .channel1
+        .configure(
+            peripherals.GPIO5,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+
+    let mut chase = sk6812::Strip::new(channel0);
+    let mut steps = sk6812::Strip::new(channel1);
+
+    let colors = [(10, 0, 0, 0), (0, 10, 0, 0), (0, 0, 10, 0), (0, 0, 0, 10)];
+
+    let mut frame_count = 0u32;
+    let mut head = 0;
+    let mut color = 0;
+    let mut slow_wait = 0;
+
+    println!("Chase on GPIO4, color steps on GPIO5");
+
+    loop {
+        let mut frame = [(0, 0, 0, 0); NUM_LEDS];
+        frame[head] = (0, 0, 0, 20);
+        frame[(head + NUM_LEDS - 1) % NUM_LEDS] = (0, 0, 0, 5);
+        chase.flush(&frame).await;
+        head = (head + 1) % NUM_LEDS;
+
+        if slow_wait == 0 {
+            slow_wait = SLOW_EVERY;
+            steps.flush(&[colors[color]; NUM_LEDS]).await;
+            color = (color + 1) % colors.len();
+            println!("Frame {}: second strip flushed", frame_count);
+        }
+        slow_wait -= 1;
+
+        frame_count += 1;
+        Timer::after(FRAME_TIME).await;
+    }
+}
@@ -0,0 +1,214 @@
+//! Stores AHT20 calibration offsets in an external AT24C32 I2C EEPROM
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - AT24C32 (e.g. on a DS3231 RTC module) and AHT20 on the same bus
+//!
+//! The AT24C32 sits at 0x50 (A0..A2 low) and takes a 16-bit memory address
+//! before the data. Two things make writes different from reads:
+//! - A write may not cross a 32-byte page, bytes past the page end wrap
+//!   around to its start. `Eeprom::write` splits writes at page boundaries.
+//! - After each page write the chip is busy for up to 10ms and does not ACK
+//!   its address. Instead of a fixed delay `wait_ready` polls until it ACKs.
+//!
+//! The record is placed at 0x001C on purpose so it straddles the page
+//! boundary at 0x0020.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner, Timeout,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+
+const EEPROM_ADDR: u8 = 0x50;
+const PAGE_SIZE: u16 = 32;
+const WRITE_CYCLE_TIMEOUT_MS: u64 = 20; // Datasheet max is 10ms
+
+const CALIBRATION_ADDR: u16 = 0x001C;
+const CALIBRATION_MAGIC: u8 = 0xA5;
+const RECORD_SIZE: usize = 10;
+
+#[derive(Debug)]
+enum EepromError {
+    I2c(I2cError),
+    /// Still busy with the previous write cycle after the timeout
+    Timeout,
+}
+
+impl From<I2cError> for EepromError {
+    fn from(e: I2cError) -> Self {
+        EepromError::I2c(e)
+    }
+}
+
+struct Eeprom {
+    addr: u8,
+}
+
+impl Eeprom {
+    fn read(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        mem_addr: u16,
+        buffer: &mut [u8],
+    ) -> Result<(), EepromError> {
+        // Sequential reads are not limited to a page
+        i2c.write_read(self.addr, &mem_addr.to_be_bytes(), buffer)?;
+        Ok(())
+    }
+
+    fn write(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        mut mem_addr: u16,
+        mut data: &[u8],
+    ) -> Result<(), EepromError> {
+        while !data.is_empty() {
+            let room = (PAGE_SIZE - mem_addr % PAGE_SIZE) as usize;
+            let (chunk, rest) = data.split_at(room.min(data.len()));
+
+            let mut frame = [0u8; 2 + PAGE_SIZE as usize];
+            frame[..2].copy_from_slice(&mem_addr.to_be_bytes());
+            frame[2..2 + chunk.len()].copy_from_slice(chunk);
+            i2c.write(self.addr, &frame[..2 + chunk.len()])?;
+            self.wait_ready(i2c)?;
+
+            mem_addr += chunk.len() as u16;
+            data = rest;
+        }
+        Ok(())
+    }
+
+    /// ACK polling: the chip ignores its address until the internal write
+    /// cycle is done, so keep addressing it until it answers
+    fn wait_ready(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), EepromError> {
+        let timeout = Timeout::from_millis(WRITE_CYCLE_TIMEOUT_MS);
+        loop {
+            if i2c.write(self.addr, &[0, 0]).is_ok() {
+                return Ok(());
+            }
+            if timeout.expired() {
+                return Err(EepromError::Timeout);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    temperature_offset: f32,
+    humidity_offset: f32,
+}
+
+impl Calibration {
+    /// Layout: magic, 4 bytes temperature_offset, 4 bytes humidity_offset
+    /// (little-endian), then a checksum byte (sum of the others)
+    fn to_bytes(self) -> [u8; RECORD_SIZE] {
+        let mut bytes = [0u8; RECORD_SIZE];
+        bytes[0] = CALIBRATION_MAGIC;
+        bytes[1..5].copy_from_slice(&self.temperature_offset.to_le_bytes());
+        bytes[5..9].copy_from_slice(&self.humidity_offset.to_le_bytes());
+        bytes[9] = checksum(&bytes[..9]);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Option<Self> {
+        if bytes[0] != CALIBRATION_MAGIC || bytes[9] != checksum(&bytes[..9]) {
+            return None;
+        }
+        Some(Calibration {
+            temperature_offset: f32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]),
+            humidity_offset: f32::from_le_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]),
+        })
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |sum, b| sum.wrapping_add(*b))
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let eeprom = Eeprom { addr: EEPROM_ADDR };
+
+    let mut bytes = [0u8; RECORD_SIZE];
+    let stored = match eeprom.read(&mut i2c, CALIBRATION_ADDR, &mut bytes) {
+        Ok(_) => Calibration::from_bytes(&bytes),
+        Err(e) => panic!("Failed to read EEPROM: {:?}", e),
+    };
+
+    let calibration = match stored {
+        Some(calibration) => {
+            println!("Loaded from EEPROM: {:?}", calibration);
+            calibration
+        }
+        None => {
+            let calibration = Calibration {
+                temperature_offset: -0.5,
+                humidity_offset: 1.5,
+            };
+            println!("No valid record, writing {:?}", calibration);
+            if let Err(e) = eeprom.write(&mut i2c, CALIBRATION_ADDR, &calibration.to_bytes()) {
+                panic!("Failed to write EEPROM: {:?}", e);
+            }
+
+            // Read back to verify the write across the page boundary
+            let mut check = [0u8; RECORD_SIZE];
+            match eeprom.read(&mut i2c, CALIBRATION_ADDR, &mut check) {
+                Ok(_) if check == calibration.to_bytes() => println!("Verified"),
+                Ok(_) => println!("Read back does not match what was written"),
+                Err(e) => println!("Failed to read back: {:?}", e),
+            }
+            calibration
+        }
+    };
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => println!(
+                "Temperature: {:.2} °C, Humidity: {:.2} % (calibrated)",
+                temperature + calibration.temperature_offset,
+                humidity + calibration.humidity_offset
+            ),
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
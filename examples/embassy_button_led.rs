@@ -0,0 +1,116 @@
+//! Changes an LED strip animation with a button, both running concurrently
+//!
+//! Connect a sk6812 RGBW LED strip to GPIO4.
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//! - Button => GPIO0 -> GND
+//!
+//! The button task sleeps in `wait_for_falling_edge().await` instead of
+//! polling and hands the new mode to the animation through a `Signal`.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
+use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    gpio::{Input, InputConfig, Pull},
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Chase,
+    Breathe,
+    Off,
+}
+
+impl Mode {
+    fn next(self) -> Self {
+        match self {
+            Mode::Chase => Mode::Breathe,
+            Mode::Breathe => Mode::Off,
+            Mode::Off => Mode::Chase,
+        }
+    }
+}
+
+static MODE: Signal<CriticalSectionRawMutex, Mode> = Signal::new();
+
+#[embassy_executor::task]
+async fn button_task(mut button: Input<'static>) {
+    let mut mode = Mode::Chase;
+    loop {
+        button.wait_for_falling_edge().await;
+        mode = mode.next();
+        println!("Mode: {:?}", mode);
+        MODE.signal(mode);
+
+        // Debounce: ignore the bouncing, then wait for the release
+        Timer::after(Duration::from_millis(50)).await;
+        button.wait_for_high().await;
+    }
+}
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let button = Input::new(peripherals.GPIO0, in_config);
+    spawner.spawn(button_task(button)).unwrap();
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    let mut mode = Mode::Chase;
+    let mut frame: u32 = 0;
+
+    loop {
+        if let Some(new_mode) = MODE.try_take() {
+            mode = new_mode;
+            frame = 0;
+        }
+
+        let mut pixels = [(0, 0, 0, 0); NUM_LEDS];
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = match mode {
+                Mode::Chase if i == frame as usize % NUM_LEDS => (0, 0, 20, 0),
+                Mode::Chase => (0, 0, 0, 0),
+                Mode::Breathe => {
+                    // Triangle wave 0..=20..=0
+                    let step = (frame % 40) as u8;
+                    let level = if step < 20 { step } else { 40 - step };
+                    (level, 0, level, 0)
+                }
+                Mode::Off => (0, 0, 0, 0),
+            };
+        }
+        strip.flush(&pixels).await;
+
+        frame = frame.wrapping_add(1);
+        Timer::after(Duration::from_millis(100)).await;
+    }
+}
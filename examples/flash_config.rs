@@ -0,0 +1,147 @@
+//! Stores a versioned configuration struct in flash
+//!
+//! No wiring needed.
+//!
+//! The config record starts with a magic byte and a schema version, so a
+//! firmware update that adds fields can still read what an older firmware
+//! wrote. An erased (all 0xFF) or unknown record falls back to defaults
+//! instead of being interpreted as garbage.
+//!
+//! The example writes a v1 record, reads it back with the current (v2)
+//! layout where the new field gets its default, then saves it as v2.
+
+#![no_std]
+#![no_main]
+
+use embedded_storage::{ReadStorage, Storage};
+use esp32_c3_super_mini_rust::{flash::CONFIG_ADDR, print_banner};
+use esp_backtrace as _;
+use esp_hal::{delay::Delay, main};
+use esp_println::println;
+use esp_storage::FlashStorage;
+
+const CONFIG_MAGIC: u8 = 0xC3;
+const CONFIG_VERSION: u8 = 2;
+const RECORD_SIZE: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    /// Schema version the record was stored with
+    version: u8,
+    temperature_offset: f32,
+    humidity_offset: f32,
+    /// Added in v2
+    report_interval_s: u16,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration {
+            version: CONFIG_VERSION,
+            temperature_offset: 0.0,
+            humidity_offset: 0.0,
+            report_interval_s: 60,
+        }
+    }
+}
+
+impl Calibration {
+    /// Layout (little-endian):
+    /// - 0: magic
+    /// - 1: version
+    /// - 2..6: temperature_offset (v1+)
+    /// - 6..10: humidity_offset (v1+)
+    /// - 10..12: report_interval_s (v2+)
+    fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Self {
+        let mut calibration = Calibration::default();
+
+        if bytes[0] != CONFIG_MAGIC {
+            println!("No config stored, using defaults");
+            return calibration;
+        }
+
+        let f32_at =
+            |i: usize| f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+
+        match bytes[1] {
+            1 => {
+                println!("Migrating config from v1");
+                calibration.version = 1;
+                calibration.temperature_offset = f32_at(2);
+                calibration.humidity_offset = f32_at(6);
+            }
+            2 => {
+                calibration.temperature_offset = f32_at(2);
+                calibration.humidity_offset = f32_at(6);
+                calibration.report_interval_s = u16::from_le_bytes([bytes[10], bytes[11]]);
+            }
+            version => {
+                println!("Unknown config version {}, using defaults", version);
+            }
+        }
+
+        calibration
+    }
+
+    fn to_bytes(&self) -> [u8; RECORD_SIZE] {
+        let mut bytes = [0xFF; RECORD_SIZE];
+        bytes[0] = CONFIG_MAGIC;
+        bytes[1] = CONFIG_VERSION;
+        bytes[2..6].copy_from_slice(&self.temperature_offset.to_le_bytes());
+        bytes[6..10].copy_from_slice(&self.humidity_offset.to_le_bytes());
+        bytes[10..12].copy_from_slice(&self.report_interval_s.to_le_bytes());
+        bytes
+    }
+
+    fn load(flash: &mut FlashStorage) -> Self {
+        let mut bytes = [0u8; RECORD_SIZE];
+        match flash.read(CONFIG_ADDR, &mut bytes) {
+            Ok(_) => Calibration::from_bytes(&bytes),
+            Err(e) => {
+                println!("Failed to read config: {:?}, using defaults", e);
+                Calibration::default()
+            }
+        }
+    }
+
+    fn save(&self, flash: &mut FlashStorage) {
+        if let Err(e) = flash.write(CONFIG_ADDR, &self.to_bytes()) {
+            println!("Failed to write config: {:?}", e);
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let _peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut flash = FlashStorage::new();
+
+    println!("Stored: {:?}", Calibration::load(&mut flash));
+
+    // Simulate a record written by the previous (v1) firmware
+    let mut v1 = [0xFF; RECORD_SIZE];
+    v1[0] = CONFIG_MAGIC;
+    v1[1] = 1;
+    v1[2..6].copy_from_slice(&(-1.5f32).to_le_bytes());
+    v1[6..10].copy_from_slice(&(2.0f32).to_le_bytes());
+    if let Err(e) = flash.write(CONFIG_ADDR, &v1) {
+        println!("Failed to write v1 config: {:?}", e);
+    }
+
+    let calibration = Calibration::load(&mut flash);
+    println!(
+        "Loaded v{} record as v{}: {:?}",
+        calibration.version, CONFIG_VERSION, calibration
+    );
+
+    // Re-save so the next boot reads a v2 record directly
+    calibration.save(&mut flash);
+    println!("Saved: {:?}", Calibration::load(&mut flash));
+
+    let delay = Delay::new();
+    loop {
+        delay.delay_millis(1000);
+    }
+}
@@ -0,0 +1,105 @@
+//! Detects the mains frequency from the flicker of artificial light
+//!
+//! The following wiring is assumed:
+//! - 3V3 => phototransistor (e.g. TEPT5700) => GPIO3 => 10k => GND
+//!
+//! Lamps on AC mains brighten and dim twice per mains cycle, so the light
+//! flickers at 100 Hz on 50 Hz mains and at 120 Hz on 60 Hz mains. An LDR is
+//! far too slow to follow that, use a phototransistor or a photodiode.
+//!
+//! Every block the ADC is sampled at `SAMPLE_RATE_HZ`, well above twice the
+//! flicker frequency. The block's mean (the steady part of the light) is
+//! subtracted, and the zero crossings of what is left are counted. A small
+//! hysteresis band keeps noise around zero from adding crossings. Two
+//! crossings make one flicker period. Daylight or LED lamps with good
+//! drivers have no flicker, then the ripple stays under `MIN_RIPPLE` and
+//! nothing is reported.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    main,
+    time::{Duration, Instant},
+};
+use esp_println::println;
+
+const SAMPLE_RATE_HZ: u32 = 1000;
+const BLOCK_LEN: usize = 500; // Half a second per block
+const OVERSAMPLING: u32 = 4;
+// Counts around the mean that do not count as a crossing
+const HYSTERESIS: i32 = 8;
+// Peak-to-peak counts below which there is no flicker worth measuring
+const MIN_RIPPLE: i32 = 30;
+
+/// Dominant frequency in Hz from zero crossings of the mean-free signal, or
+/// `None` when the ripple is too small
+fn flicker_frequency(samples: &[u16]) -> Option<f32> {
+    let mean = (samples.iter().map(|&s| s as u32).sum::<u32>() / samples.len() as u32) as i32;
+
+    let (min, max) = samples
+        .iter()
+        .fold((u16::MAX, 0), |(min, max), &s| (min.min(s), max.max(s)));
+    if (max as i32 - min as i32) < MIN_RIPPLE {
+        return None;
+    }
+
+    let mut above = samples[0] as i32 > mean;
+    let mut crossings = 0u32;
+    for &sample in samples {
+        let ac = sample as i32 - mean;
+        if above && ac < -HYSTERESIS {
+            above = false;
+            crossings += 1;
+        } else if !above && ac > HYSTERESIS {
+            above = true;
+            crossings += 1;
+        }
+    }
+
+    let block_secs = samples.len() as f32 / SAMPLE_RATE_HZ as f32;
+    Some(crossings as f32 / 2.0 / block_secs)
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let delay = Delay::new();
+    let period = Duration::from_micros(1_000_000 / SAMPLE_RATE_HZ as u64);
+    let mut samples = [0u16; BLOCK_LEN];
+
+    loop {
+        // Sample on a fixed schedule, not with a fixed delay after each
+        // read, so the ADC conversion time does not skew the rate
+        let mut next = Instant::now();
+        for sample in samples.iter_mut() {
+            while Instant::now() < next {}
+            next += period;
+
+            let sum: u32 = (0..OVERSAMPLING)
+                .map(|_| nb::block!(adc1.read_oneshot(&mut pin)).unwrap() as u32)
+                .sum();
+            *sample = (sum / OVERSAMPLING) as u16;
+        }
+
+        match flicker_frequency(&samples) {
+            Some(flicker) => {
+                let mains = if flicker > 110.0 { 60 } else { 50 };
+                println!("Flicker: {:.1} Hz, mains: {} Hz", flicker, mains);
+            }
+            None => println!("No flicker detected"),
+        }
+
+        delay.delay_millis(500);
+    }
+}
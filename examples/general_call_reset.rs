@@ -0,0 +1,102 @@
+//! Resets all devices on the I2C bus with a general call before using them
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - AHT20 and/or other I2C devices on the bus
+//!
+//! A write of 0x06 to the general call address 0x00 asks every device that
+//! implements it to reset as if power cycled. It is part of the I2C spec
+//! but optional, and of the sensors used in these examples none honor it:
+//! the AHT20 has its own soft reset (0xBA, sent below), the BH1750 has
+//! 0x07 which only clears its data register, and the SH1106 and AT24C32
+//! ignore it. Sensors that do implement it include the SHT3x/SHT4x and
+//! many ADC/DAC chips (MCP4725, ADS1115). Sending it anyway does no harm.
+//!
+//! When no device implements the general call, nobody ACKs it and the
+//! write fails with an address NACK. That is normal, not an error.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{AcknowledgeCheckFailedReason, Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+
+// Broadcast the reset before initializing the sensors
+const GENERAL_CALL_ON_BOOT: bool = true;
+
+const GENERAL_CALL_ADDR: u8 = 0x00;
+const GENERAL_CALL_RESET: u8 = 0x06;
+
+/// Broadcasts the general call reset. Returns whether any device ACKed it,
+/// a NACK only means no device on the bus implements the general call.
+fn i2c_general_call_reset(i2c: &mut I2c<'_, Blocking>) -> Result<bool, I2cError> {
+    match i2c.write(GENERAL_CALL_ADDR, &[GENERAL_CALL_RESET]) {
+        Ok(_) => Ok(true),
+        Err(I2cError::AcknowledgeCheckFailed(AcknowledgeCheckFailedReason::Address)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let delay = Delay::new();
+
+    // Devices need their power-on time before they listen to anything
+    delay.delay_millis(40);
+
+    if GENERAL_CALL_ON_BOOT {
+        match i2c_general_call_reset(&mut i2c) {
+            Ok(true) => println!("General call reset acknowledged"),
+            Ok(false) => println!("No device answers the general call, continuing"),
+            Err(e) => println!("General call reset failed: {:?}", e),
+        }
+        // Resets take up to a few ms, wait before talking to anyone
+        delay.delay_millis(20);
+    }
+
+    // The AHT20 ignores the general call, so reset it individually
+    let aht20 = Aht20::new(delay).with_reset_on_init(true);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => println!(
+                "Temperature: {:.2} °C, Humidity: {:.2} %",
+                temperature, humidity
+            ),
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
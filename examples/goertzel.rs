@@ -0,0 +1,149 @@
+//! Detects a single tone in ADC samples with the Goertzel algorithm
+//!
+//! The following wiring is assumed:
+//! - microphone module output (MAX4466, MAX9814 or similar, biased to
+//!   mid-supply) => GPIO3
+//!
+//! Goertzel computes one bin of a DFT with a two-tap filter: one multiply
+//! and two adds per sample, no buffers of complex numbers, no FFT. It is
+//! the usual way to look for a handful of known frequencies (DTMF, a
+//! whistle, a smoke alarm beep).
+//!
+//! How the numbers relate:
+//! - `SAMPLE_RATE_HZ` must be more than twice `TARGET_HZ`
+//! - one block of `BLOCK_LEN` samples takes `BLOCK_LEN / SAMPLE_RATE_HZ`
+//!   seconds (25 ms here), which is also the detection latency
+//! - the bin is `SAMPLE_RATE_HZ / BLOCK_LEN` wide (40 Hz here): tones that
+//!   far from the target still register, tones further away do not.
+//!   Longer blocks give narrower bins but react slower.
+//! - the target is rounded to the nearest bin, `k = BLOCK_LEN * TARGET_HZ /
+//!   SAMPLE_RATE_HZ`, pick values where that comes out whole
+//!
+//! The bin power is divided by the block's total energy, so the result is
+//! the fraction of the signal at the target frequency, 0.0..1.0, whatever
+//! the volume. Blocks quieter than `MIN_ENERGY` are ignored, silence would
+//! otherwise give random ratios.
+
+#![no_std]
+#![no_main]
+
+use core::f32::consts::PI;
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    main,
+    time::{Duration, Instant},
+};
+use esp_println::println;
+
+const SAMPLE_RATE_HZ: u32 = 8000;
+const BLOCK_LEN: usize = 200;
+const TARGET_HZ: u32 = 1000;
+
+// Share of the energy in the target bin needed to count as detected
+const THRESHOLD: f32 = 0.3;
+// Mean squared deviation (raw ADC units) below which a block is silence
+const MIN_ENERGY: f32 = 100.0;
+
+/// Cosine by Taylor series after reducing to -pi..pi, enough for the
+/// coefficient here (no `f32::cos` without std)
+fn cos(x: f32) -> f32 {
+    let mut x = x % (2.0 * PI);
+    if x > PI {
+        x -= 2.0 * PI;
+    } else if x < -PI {
+        x += 2.0 * PI;
+    }
+    let x2 = x * x;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..10 {
+        term *= -x2 / ((2 * n - 1) * (2 * n)) as f32;
+        sum += term;
+    }
+    sum
+}
+
+struct Goertzel {
+    coeff: f32,
+}
+
+impl Goertzel {
+    fn new(target_hz: u32, sample_rate_hz: u32, block_len: usize) -> Self {
+        // Nearest bin to the target
+        let k = (block_len as u32 * target_hz + sample_rate_hz / 2) / sample_rate_hz;
+        let omega = 2.0 * PI * k as f32 / block_len as f32;
+        Goertzel {
+            coeff: 2.0 * cos(omega),
+        }
+    }
+
+    /// Share of the block's energy in the target bin, 0.0..1.0. `None` for
+    /// blocks quieter than `MIN_ENERGY`.
+    fn detect(&self, samples: &[u16]) -> Option<f32> {
+        let n = samples.len() as f32;
+        let mean = samples.iter().map(|&s| s as f32).sum::<f32>() / n;
+
+        let (mut s1, mut s2) = (0.0f32, 0.0f32);
+        let mut energy = 0.0f32;
+        for &sample in samples {
+            // Without the DC offset of the biased microphone
+            let x = sample as f32 - mean;
+            energy += x * x;
+            let s0 = x + self.coeff * s1 - s2;
+            s2 = s1;
+            s1 = s0;
+        }
+
+        if energy / n < MIN_ENERGY {
+            return None;
+        }
+        let power = s1 * s1 + s2 * s2 - self.coeff * s1 * s2;
+        // A pure tone in the bin gives power = energy * n / 2
+        Some(power / (energy * n / 2.0))
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let goertzel = Goertzel::new(TARGET_HZ, SAMPLE_RATE_HZ, BLOCK_LEN);
+    let period = Duration::from_micros(1_000_000 / SAMPLE_RATE_HZ as u64);
+    let mut samples = [0u16; BLOCK_LEN];
+    let mut detected = false;
+
+    println!(
+        "Listening for {} Hz, bin width {} Hz",
+        TARGET_HZ,
+        SAMPLE_RATE_HZ / BLOCK_LEN as u32
+    );
+
+    loop {
+        // Sample on a fixed schedule, the sample rate has to be exact for
+        // the bin to sit at the right frequency
+        let mut next = Instant::now();
+        for sample in samples.iter_mut() {
+            while Instant::now() < next {}
+            *sample = nb::block!(adc1.read_oneshot(&mut pin)).unwrap();
+            next += period;
+        }
+
+        let ratio = goertzel.detect(&samples);
+        let now_detected = ratio.is_some_and(|r| r >= THRESHOLD);
+        if now_detected != detected {
+            detected = now_detected;
+            match ratio {
+                Some(r) if detected => println!("{} Hz detected ({:.2})", TARGET_HZ, r),
+                _ => println!("{} Hz gone", TARGET_HZ),
+            }
+        }
+    }
+}
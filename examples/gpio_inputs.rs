@@ -0,0 +1,87 @@
+//! Shows the three input pull configurations side by side
+//!
+//! The following wiring is assumed:
+//! - Button => GPIO0 -> GND (active low, internal pull-up)
+//! - Active-high sensor output (PIR, hall switch, ...) => GPIO1 (internal pull-down)
+//! - GPIO3 left unconnected (floating, no pull)
+//!
+//! Which pull to use depends on what the other side does when it is idle:
+//! - `Pull::Up` for anything that pulls the line to GND when active: buttons
+//!   to GND, open-drain/open-collector outputs. Reads high when idle.
+//! - `Pull::Down` for anything that drives the line high when active and
+//!   leaves it open otherwise: buttons to 3V3, some sensor modules.
+//! - `Pull::None` only when something always drives the line (push-pull
+//!   output, external resistor). A floating input picks up noise, touching
+//!   the wire is enough to flip it, and with interrupts enabled it fires
+//!   phantom events. The counter for GPIO3 shows that.
+//!
+//! Avoid the strapping pins GPIO2, GPIO8 and GPIO9 for inputs that might be
+//! pulled the wrong way during reset, they decide the boot mode.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Level, Pull},
+    main,
+};
+use esp_println::println;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let button = Input::new(
+        peripherals.GPIO0,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+    let sensor = Input::new(
+        peripherals.GPIO1,
+        InputConfig::default().with_pull(Pull::Down),
+    );
+    let floating = Input::new(
+        peripherals.GPIO3,
+        InputConfig::default().with_pull(Pull::None),
+    );
+
+    let delay = Delay::new();
+
+    let mut last_floating = floating.level();
+    let mut floating_changes = 0u32;
+    let mut ticks = 0u32;
+
+    loop {
+        // Sample the floating pin often to catch its random flips
+        let level = floating.level();
+        if level != last_floating {
+            floating_changes += 1;
+            last_floating = level;
+        }
+
+        ticks += 1;
+        if ticks == 100 {
+            ticks = 0;
+            println!(
+                "Button (pull-up): {}, Sensor (pull-down): {}, Floating: {:?} ({} changes)",
+                if button.is_low() {
+                    "pressed"
+                } else {
+                    "released"
+                },
+                if sensor.level() == Level::High {
+                    "active"
+                } else {
+                    "idle"
+                },
+                level,
+                floating_changes
+            );
+        }
+
+        delay.delay_millis(5);
+    }
+}
@@ -0,0 +1,84 @@
+//! Benchmarks I2C throughput at different bus frequencies
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - AHT20 (or any device answering at TARGET_ADDR) on the bus
+//!
+//! Each speed does TRANSACTIONS register reads (write the register, read one
+//! byte back) and reports transactions per second and the errors seen.
+//! The C3 supports up to 800kHz, faster modes are not available. Higher
+//! speeds need stronger pull-ups and short wires, watch the error count.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::{Instant, Rate},
+    Blocking,
+};
+use esp_println::println;
+
+const TARGET_ADDR: u8 = 0x38; // AHT20
+const TARGET_REG: u8 = 0x71; // AHT20 status word
+const TRANSACTIONS: u32 = 1000;
+const SPEEDS_KHZ: [u32; 3] = [100, 400, 800];
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let mut i2c: I2c<'_, Blocking> = match I2c::new(peripherals.I2C0, I2cConfig::default()) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    loop {
+        for khz in SPEEDS_KHZ {
+            let config = I2cConfig::default().with_frequency(Rate::from_khz(khz));
+            if let Err(e) = i2c.apply_config(&config) {
+                println!("{} kHz: not supported ({:?})", khz, e);
+                continue;
+            }
+
+            let mut nacks = 0;
+            let mut other_errors = 0;
+            let mut status = [0u8];
+
+            let start = Instant::now();
+            for _ in 0..TRANSACTIONS {
+                match i2c.write_read(TARGET_ADDR, &[TARGET_REG], &mut status) {
+                    Ok(_) => {}
+                    Err(I2cError::AcknowledgeCheckFailed(_)) => nacks += 1,
+                    Err(_) => other_errors += 1,
+                }
+            }
+            let elapsed_us = (Instant::now() - start).as_micros();
+
+            let per_second = TRANSACTIONS as u64 * 1_000_000 / elapsed_us.max(1);
+            println!(
+                "{} kHz: {} transactions/s, {} us each, {} NACKs, {} other errors",
+                khz,
+                per_second,
+                elapsed_us / TRANSACTIONS as u64,
+                nacks,
+                other_errors
+            );
+        }
+        println!();
+
+        delay.delay_millis(5000);
+    }
+}
@@ -0,0 +1,205 @@
+//! Detects whether an I2C device stretches the clock and for how long
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - AHT20 (or the device at TARGET_ADDR) on the bus
+//!
+//! Before the pins are handed to the I2C peripheral, a register read is
+//! bit-banged in open-drain mode. Every time SCL is released we measure how
+//! long the device keeps holding it low. A device that stretches the clock
+//! for long may need a lower bus speed or a longer timeout. Afterwards the
+//! pins are given to the I2C peripheral to show the device still works.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, Timeout};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Flex, Pull},
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+    Blocking,
+};
+use esp_println::println;
+
+const TARGET_ADDR: u8 = 0x38; // AHT20
+const TARGET_REG: u8 = 0x71; // AHT20 status word
+const HALF_PERIOD_US: u32 = 10; // ~50kHz, slow enough to see any stretching
+const STRETCH_TIMEOUT: Duration = Duration::from_millis(50);
+// Rise time and polling overhead also show up as a short "stretch"
+const STRETCH_THRESHOLD_US: u64 = 5;
+
+#[derive(Debug)]
+enum ProbeError {
+    /// SCL never went high, either stuck or stretched beyond the timeout
+    SclStuckLow,
+    Nack,
+}
+
+struct BitBang<'a> {
+    sda: Flex<'a>,
+    scl: Flex<'a>,
+    delay: Delay,
+    max_stretch_us: u64,
+    stretched_clocks: u32,
+}
+
+impl<'a> BitBang<'a> {
+    fn new(mut sda: Flex<'a>, mut scl: Flex<'a>) -> Self {
+        sda.set_as_open_drain(Pull::Up);
+        scl.set_as_open_drain(Pull::Up);
+        sda.set_high();
+        scl.set_high();
+        BitBang {
+            sda,
+            scl,
+            delay: Delay::new(),
+            max_stretch_us: 0,
+            stretched_clocks: 0,
+        }
+    }
+
+    /// Releases SCL and waits until the device lets it go high
+    fn release_scl(&mut self) -> Result<(), ProbeError> {
+        self.scl.set_high();
+        let start = Instant::now();
+        let timeout = Timeout::new(STRETCH_TIMEOUT);
+        while self.scl.is_low() {
+            if timeout.expired() {
+                return Err(ProbeError::SclStuckLow);
+            }
+        }
+        let held_us = (Instant::now() - start).as_micros();
+        if held_us > STRETCH_THRESHOLD_US {
+            self.stretched_clocks += 1;
+        }
+        self.max_stretch_us = self.max_stretch_us.max(held_us);
+        Ok(())
+    }
+
+    fn start(&mut self) -> Result<(), ProbeError> {
+        self.sda.set_high();
+        self.release_scl()?;
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.sda.set_low();
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.scl.set_low();
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), ProbeError> {
+        self.sda.set_low();
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.release_scl()?;
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.sda.set_high();
+        self.delay.delay_micros(HALF_PERIOD_US);
+        Ok(())
+    }
+
+    fn clock_bit(&mut self, bit: bool) -> Result<bool, ProbeError> {
+        if bit {
+            self.sda.set_high();
+        } else {
+            self.sda.set_low();
+        }
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.release_scl()?;
+        let read = self.sda.is_high();
+        self.delay.delay_micros(HALF_PERIOD_US);
+        self.scl.set_low();
+        Ok(read)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), ProbeError> {
+        for bit in (0..8).rev() {
+            self.clock_bit(byte & (1 << bit) != 0)?;
+        }
+        // Release SDA, the device pulls it low to ACK
+        if self.clock_bit(true)? {
+            return Err(ProbeError::Nack);
+        }
+        Ok(())
+    }
+
+    fn read_byte(&mut self, ack: bool) -> Result<u8, ProbeError> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = (byte << 1) | self.clock_bit(true)? as u8;
+        }
+        self.clock_bit(!ack)?;
+        Ok(byte)
+    }
+
+    fn read_register(&mut self, addr: u8, reg: u8) -> Result<u8, ProbeError> {
+        self.start()?;
+        self.write_byte(addr << 1)?;
+        self.write_byte(reg)?;
+        self.start()?;
+        self.write_byte((addr << 1) | 1)?;
+        let value = self.read_byte(false)?;
+        self.stop()?;
+        Ok(value)
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let mut peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    {
+        let mut probe = BitBang::new(
+            Flex::new(&mut peripherals.GPIO8),
+            Flex::new(&mut peripherals.GPIO9),
+        );
+        match probe.read_register(TARGET_ADDR, TARGET_REG) {
+            Ok(value) => {
+                println!("Probe read 0x{:02x} from 0x{:02x}", value, TARGET_ADDR);
+                if probe.stretched_clocks == 0 {
+                    println!("No clock stretching detected");
+                } else {
+                    println!(
+                        "Device stretched {} clocks, longest {} us",
+                        probe.stretched_clocks, probe.max_stretch_us
+                    );
+                }
+            }
+            Err(ProbeError::SclStuckLow) => {
+                println!(
+                    "SCL held low for more than {} ms, check wiring or pull-ups",
+                    STRETCH_TIMEOUT.as_millis()
+                );
+            }
+            Err(e) => {
+                println!("Probe failed: {:?}", e);
+            }
+        }
+        // Pins are released here
+    }
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c: I2c<'_, Blocking> = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    loop {
+        let mut value = [0u8];
+        match i2c.write_read(TARGET_ADDR, &[TARGET_REG], &mut value) {
+            Ok(_) => println!("I2C read 0x{:02x}", value[0]),
+            Err(e) => println!("I2C read failed: {:?}", e),
+        }
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
@@ -0,0 +1,201 @@
+//! Notices I2C devices plugged in after boot and initializes them on the fly
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - any of the devices in `KNOWN_DEVICES`, connected or not
+//!
+//! Every `RESCAN_INTERVAL_MS` each known address is probed. A device that
+//! answers for the first time (or again, after it was gone) gets its init
+//! sequence, a device that stops answering is reported as lost. Plug an
+//! AHT20 in while this runs and it logs "AHT20 detected at 0x38,
+//! initializing" and starts printing readings.
+//!
+//! Only the listed addresses are probed, not the whole bus: some devices
+//! misbehave when read at random, and this keeps the rescan quick enough to
+//! run alongside normal work. Hot-plugging on a powered bus can glitch a
+//! transfer that is in progress, the next rescan recovers from that.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{self, Aht20, Aht20Error, Measurement},
+    bh1750::{self, Bh1750},
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+    Blocking,
+};
+use esp_println::println;
+
+const AHT20_ADDR: u8 = aht20::ADDR;
+const BH1750_ADDR: u8 = bh1750::ADDR;
+const SH1106_ADDR: u8 = 0x3C;
+
+const RESCAN_INTERVAL_MS: u64 = 1000;
+const READ_INTERVAL_MS: u64 = 2000;
+
+/// Init errors of all the known devices
+#[derive(Debug)]
+enum InitError {
+    I2c(I2cError),
+    Aht20(Aht20Error),
+}
+
+impl From<I2cError> for InitError {
+    fn from(e: I2cError) -> Self {
+        InitError::I2c(e)
+    }
+}
+
+impl From<Aht20Error> for InitError {
+    fn from(e: Aht20Error) -> Self {
+        InitError::Aht20(e)
+    }
+}
+
+/// Drivers of the known devices, shared by their init and the reads so a
+/// driver is started by the same instance that reads from it
+struct Drivers {
+    aht20: Aht20,
+    bh1750: Bh1750,
+}
+
+type InitFn = fn(&mut I2c<'_, Blocking>, &Drivers) -> Result<(), InitError>;
+
+struct KnownDevice {
+    name: &'static str,
+    address: u8,
+    init: InitFn,
+}
+
+const KNOWN_DEVICES: [KnownDevice; 3] = [
+    KnownDevice {
+        name: "AHT20",
+        address: AHT20_ADDR,
+        init: init_aht20,
+    },
+    KnownDevice {
+        name: "BH1750",
+        address: BH1750_ADDR,
+        init: init_bh1750,
+    },
+    KnownDevice {
+        name: "SH1106 display",
+        address: SH1106_ADDR,
+        init: init_none,
+    },
+];
+
+fn init_aht20(i2c: &mut I2c<'_, Blocking>, drivers: &Drivers) -> Result<(), InitError> {
+    // Freshly plugged in, `start` gives it the 40 ms power-on time first
+    drivers.aht20.start(i2c)?;
+    Ok(())
+}
+
+fn init_bh1750(i2c: &mut I2c<'_, Blocking>, drivers: &Drivers) -> Result<(), InitError> {
+    // Continuous mode, so the main loop only has to fetch the latest result
+    drivers.bh1750.start(i2c)?;
+    drivers.bh1750.start_continuous(i2c)?;
+    Ok(())
+}
+
+fn init_none(_i2c: &mut I2c<'_, Blocking>, _drivers: &Drivers) -> Result<(), InitError> {
+    Ok(())
+}
+
+fn probe(i2c: &mut I2c<'_, Blocking>, address: u8) -> bool {
+    let mut byte = [0u8];
+    i2c.read(address, &mut byte).is_ok()
+}
+
+/// Probes every known device and runs the init of the ones that showed up
+fn rescan(i2c: &mut I2c<'_, Blocking>, drivers: &Drivers, ready: &mut [bool]) {
+    for (device, ready) in KNOWN_DEVICES.iter().zip(ready.iter_mut()) {
+        let present = probe(i2c, device.address);
+        if present && !*ready {
+            println!(
+                "{} detected at 0x{:02x}, initializing",
+                device.name, device.address
+            );
+            match (device.init)(i2c, drivers) {
+                Ok(_) => *ready = true,
+                // Probably still being plugged in, the next rescan tries again
+                Err(e) => println!("{} init failed: {:?}", device.name, e),
+            }
+        } else if !present && *ready {
+            println!("{} at 0x{:02x} lost", device.name, device.address);
+            *ready = false;
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let delay = Delay::new();
+    let drivers = Drivers {
+        aht20: Aht20::new(delay),
+        bh1750: Bh1750::new(delay),
+    };
+    let mut ready = [false; KNOWN_DEVICES.len()];
+    let mut next_scan = Instant::now();
+    let mut next_read = Instant::now();
+
+    println!("Watching for I2C devices, plug one in");
+
+    loop {
+        let now = Instant::now();
+
+        if now >= next_scan {
+            rescan(&mut i2c, &drivers, &mut ready);
+            next_scan = now + Duration::from_millis(RESCAN_INTERVAL_MS);
+        }
+
+        if now >= next_read {
+            next_read = now + Duration::from_millis(READ_INTERVAL_MS);
+            for (device, &ready) in KNOWN_DEVICES.iter().zip(ready.iter()) {
+                if !ready {
+                    continue;
+                }
+                match device.address {
+                    AHT20_ADDR => match drivers.aht20.read(&mut i2c) {
+                        Ok(Measurement {
+                            temperature,
+                            humidity,
+                        }) => println!(
+                            "Temperature: {:.2} °C, Humidity: {:.2} %",
+                            temperature, humidity
+                        ),
+                        Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+                    },
+                    BH1750_ADDR => match drivers.bh1750.fetch(&mut i2c) {
+                        Ok(lux) => println!("Light: {:.1} lx", lux),
+                        Err(e) => println!("Failed to read data from BH1750: {:?}", e),
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        delay.delay_millis(10);
+    }
+}
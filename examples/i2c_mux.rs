@@ -0,0 +1,100 @@
+//! Reads several AHT20 sensors behind a TCA9548A I2C multiplexer
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - TCA9548A on the bus at 0x70 (A0..A2 to GND)
+//! - AHT20 sensors on any of the multiplexer channels SD0/SC0 .. SD7/SC7
+//!
+//! The AHT20 has a fixed address, so two of them cannot share a bus. The
+//! multiplexer connects only one downstream channel at a time, each sensor
+//! is read after selecting its channel. Up to 8 multiplexers (0x70..0x77)
+//! can be chained the same way.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+
+const MUX_ADDR: u8 = 0x70;
+const MUX_CHANNELS: u8 = 8;
+
+/// Connects downstream channel `ch` (0..=7) to the bus, disconnecting the rest
+fn select_channel(i2c: &mut I2c<'_, Blocking>, ch: u8) -> Result<(), I2cError> {
+    assert!(ch < MUX_CHANNELS, "TCA9548A has only 8 channels");
+    i2c.write(MUX_ADDR, &[1 << ch])
+}
+
+/// Disconnects all downstream channels
+fn deselect_all(i2c: &mut I2c<'_, Blocking>) -> Result<(), I2cError> {
+    i2c.write(MUX_ADDR, &[0])
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    // Find out which channels have a sensor and initialize it
+    let aht20 = Aht20::new(delay);
+    let mut present = [false; MUX_CHANNELS as usize];
+    for ch in 0..MUX_CHANNELS {
+        if let Err(e) = select_channel(&mut i2c, ch) {
+            panic!("TCA9548A not responding at 0x{:02x}: {:?}", MUX_ADDR, e);
+        }
+        if aht20.start(&mut i2c).is_ok() {
+            println!("Channel {}: AHT20 found", ch);
+            present[ch as usize] = true;
+        }
+    }
+
+    loop {
+        for ch in 0..MUX_CHANNELS {
+            if !present[ch as usize] {
+                continue;
+            }
+            if let Err(e) = select_channel(&mut i2c, ch) {
+                println!("Failed to select channel {}: {:?}", ch, e);
+                continue;
+            }
+            match aht20.read(&mut i2c) {
+                Ok(Measurement {
+                    temperature,
+                    humidity,
+                }) => println!(
+                    "Channel {}: Temperature: {:.2} °C, Humidity: {:.2} %",
+                    ch, temperature, humidity
+                ),
+                Err(e) => println!("Channel {}: failed to read AHT20: {:?}", ch, e),
+            }
+        }
+        deselect_all(&mut i2c).ok();
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
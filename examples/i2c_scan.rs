@@ -0,0 +1,69 @@
+//! Scans the I2C bus and recognizes swapped SDA and SCL wires
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - any I2C devices on the bus
+//!
+//! Every address from 0x08 to 0x77 is probed with a one byte read, the ones
+//! that ACK are listed. If nothing answers, the scan is repeated with the
+//! two pins swapped in the GPIO matrix. Devices showing up only then means
+//! the SDA and SCL wires are crossed, which otherwise just looks like a dead
+//! bus.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{i2c_diag, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Pull},
+    main,
+};
+use esp_println::{print, println};
+
+#[main]
+fn main() -> ! {
+    let mut peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    loop {
+        // With pull-ups both lines idle high, a line stuck low blocks every
+        // transfer no matter how the pins are assigned
+        {
+            let config = InputConfig::default().with_pull(Pull::None);
+            let sda = Input::new(&mut peripherals.GPIO8, config);
+            let scl = Input::new(&mut peripherals.GPIO9, config);
+            if sda.is_low() || scl.is_low() {
+                println!(
+                    "Warning: SDA {:?}, SCL {:?} while idle, check the pull-ups or a device holding the bus",
+                    sda.level(),
+                    scl.level()
+                );
+            }
+        }
+
+        print!("Devices found:");
+        let found = i2c_diag::scan_pins(
+            &mut peripherals.I2C0,
+            &mut peripherals.GPIO8,
+            &mut peripherals.GPIO9,
+            |addr| print!(" 0x{:02x}", addr),
+        );
+        println!();
+
+        if found == 0 {
+            println!("No devices on SDA=GPIO8, SCL=GPIO9, trying the pins swapped");
+            i2c_diag::check_swapped(
+                &mut peripherals.I2C0,
+                &mut peripherals.GPIO8,
+                &mut peripherals.GPIO9,
+            );
+        }
+
+        delay.delay_millis(5000);
+    }
+}
@@ -0,0 +1,154 @@
+//! Reads bus voltage, current and power from an INA219
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - INA219 VIN+ => supply side, VIN- => load side (the shunt sits between them)
+//!
+//! Assumes the common breakout board: a 0.1 Ω shunt (marked R100) and
+//! A0/A1 open, so the address is 0x40. With another shunt change
+//! `SHUNT_OHMS`, and `MAX_CURRENT_A` to the largest current expected;
+//! the full range is 320 mV across the shunt, 3.2 A with 0.1 Ω.
+//!
+//! The calibration register makes the chip compute current and power
+//! itself: `cal = 0.04096 / (current_lsb * shunt)`, where `current_lsb` is
+//! `MAX_CURRENT_A / 32768` rounded up to a round number. Power then counts
+//! in steps of `20 * current_lsb`. The shunt voltage and current registers
+//! are two's complement, current flowing from VIN- to VIN+ reads negative.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+
+const INA219_ADDR: u8 = 0x40;
+const REG_CONFIG: u8 = 0x00;
+const REG_SHUNT_VOLTAGE: u8 = 0x01;
+const REG_BUS_VOLTAGE: u8 = 0x02;
+const REG_POWER: u8 = 0x03;
+const REG_CURRENT: u8 = 0x04;
+const REG_CALIBRATION: u8 = 0x05;
+
+// 32V bus range, ±320mV shunt range, 12-bit samples, continuous (the reset value)
+const CONFIG_32V_320MV: u16 = 0x399F;
+const CONFIG_RESET: u16 = 0x8000;
+
+const SHUNT_OHMS: f32 = 0.1;
+const MAX_CURRENT_A: f32 = 3.2;
+// MAX_CURRENT_A / 32768 = 97.7 µA, rounded up
+const CURRENT_LSB_A: f32 = 0.0001;
+const POWER_LSB_W: f32 = 20.0 * CURRENT_LSB_A;
+// 0.04096 / (CURRENT_LSB_A * SHUNT_OHMS) = 4096. None of the decimal
+// constants is exact in f32, so the quotient can land just below a whole
+// number, where `as` would truncate it. Round instead.
+const CALIBRATION: u16 = (0.04096 / (CURRENT_LSB_A * SHUNT_OHMS) + 0.5) as u16;
+
+// Bus voltage register flags
+const BUS_OVERFLOW: u16 = 0x0001;
+
+#[derive(Debug)]
+struct Reading {
+    bus_v: f32,
+    shunt_mv: f32,
+    current_ma: f32,
+    power_mw: f32,
+}
+
+struct Ina219<'a> {
+    i2c: I2c<'a, Blocking>,
+}
+
+impl<'a> Ina219<'a> {
+    fn new(i2c: I2c<'a, Blocking>) -> Result<Self, I2cError> {
+        let mut ina = Ina219 { i2c };
+        ina.write_register(REG_CONFIG, CONFIG_RESET)?;
+        ina.write_register(REG_CONFIG, CONFIG_32V_320MV)?;
+        ina.write_register(REG_CALIBRATION, CALIBRATION)?;
+        Ok(ina)
+    }
+
+    fn write_register(&mut self, register: u8, value: u16) -> Result<(), I2cError> {
+        let [msb, lsb] = value.to_be_bytes();
+        self.i2c.write(INA219_ADDR, &[register, msb, lsb])
+    }
+
+    fn read_register(&mut self, register: u8) -> Result<u16, I2cError> {
+        let mut buffer = [0u8; 2];
+        self.i2c.write_read(INA219_ADDR, &[register], &mut buffer)?;
+        Ok(u16::from_be_bytes(buffer))
+    }
+
+    /// `None` when the current or power calculation overflowed, the load
+    /// draws more than the configured range
+    fn read(&mut self) -> Result<Option<Reading>, I2cError> {
+        let bus = self.read_register(REG_BUS_VOLTAGE)?;
+        if bus & BUS_OVERFLOW != 0 {
+            return Ok(None);
+        }
+        // Voltage sits in bits 15..3, 4 mV per step
+        let bus_v = (bus >> 3) as f32 * 0.004;
+
+        // 10 µV per step, signed
+        let shunt_mv = self.read_register(REG_SHUNT_VOLTAGE)? as i16 as f32 * 0.01;
+        let current_ma = self.read_register(REG_CURRENT)? as i16 as f32 * CURRENT_LSB_A * 1000.0;
+        let power_mw = self.read_register(REG_POWER)? as f32 * POWER_LSB_W * 1000.0;
+
+        Ok(Some(Reading {
+            bus_v,
+            shunt_mv,
+            current_ma,
+            power_mw,
+        }))
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let mut ina219 = match Ina219::new(i2c) {
+        Ok(ina219) => ina219,
+        Err(e) => {
+            panic!("Failed to initialize INA219: {:?}", e);
+        }
+    };
+    println!(
+        "INA219 ready, shunt {} Ω, range {} A, calibration {}",
+        SHUNT_OHMS, MAX_CURRENT_A, CALIBRATION
+    );
+
+    let delay = Delay::new();
+
+    loop {
+        match ina219.read() {
+            Ok(Some(reading)) => println!(
+                "Bus: {:.3} V, shunt: {:.2} mV, current: {:.1} mA, power: {:.1} mW",
+                reading.bus_v, reading.shunt_mv, reading.current_ma, reading.power_mw
+            ),
+            Ok(None) => println!("INA219 overflow, current above {} A", MAX_CURRENT_A),
+            Err(e) => println!("Failed to read data from INA219: {:?}", e),
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
@@ -9,6 +9,7 @@
 #![no_std]
 #![no_main]
 
+use esp32_c3_super_mini_rust::print_banner;
 use esp_backtrace as _;
 use esp_hal::{
     delay::Delay,
@@ -48,6 +49,7 @@ fn handler() {
 #[main]
 fn main() -> ! {
     let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
 
     let out_config = OutputConfig::default();
     let mut led = Output::new(peripherals.GPIO8, Level::High, out_config);
@@ -0,0 +1,158 @@
+//! Shares an AHT20 reading between interrupts and the main loop
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - Button => GPIO0 -> GND
+//!
+//! A periodic timer interrupt only raises a flag, the I2C transfer itself is
+//! too slow for an interrupt handler so the main loop does the read and
+//! stores the result in a critical-section protected global. The button
+//! interrupt is a second consumer that prints whatever reading is latest.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Event, Input, InputConfig, Io, Pull},
+    handler,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+    timer::{timg::TimerGroup, PeriodicTimer},
+    Blocking,
+};
+use esp_println::println;
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+#[derive(Clone, Copy)]
+struct Reading {
+    temperature: f32,
+    humidity: f32,
+    taken_at: Instant,
+}
+
+// global mutable state shared with the interrupt handlers
+static TIMER: Mutex<RefCell<Option<PeriodicTimer<'static, Blocking>>>> =
+    Mutex::new(RefCell::new(None));
+static BUTTON: Mutex<RefCell<Option<Input>>> = Mutex::new(RefCell::new(None));
+static READ_DUE: Mutex<RefCell<bool>> = Mutex::new(RefCell::new(false));
+static LATEST: Mutex<RefCell<Option<Reading>>> = Mutex::new(RefCell::new(None));
+
+#[handler]
+fn timer_handler() {
+    critical_section::with(|cs| {
+        if let Some(timer) = TIMER.borrow_ref_mut(cs).as_mut() {
+            timer.clear_interrupt();
+        }
+        *READ_DUE.borrow_ref_mut(cs) = true;
+    });
+}
+
+#[handler]
+fn button_handler() {
+    // Copy the reading out and print only after the critical section ends,
+    // printing inside it would block every other interrupt for the whole
+    // UART write
+    let pressed = critical_section::with(|cs| {
+        let mut button = BUTTON.borrow_ref_mut(cs);
+        let button = button.as_mut()?;
+        if !button.is_interrupt_set() {
+            return None;
+        }
+        button.clear_interrupt();
+        Some(*LATEST.borrow_ref(cs))
+    });
+
+    match pressed {
+        Some(Some(reading)) => println!(
+            "Latest: {:.2} °C, {:.2} % ({} ms old)",
+            reading.temperature,
+            reading.humidity,
+            (Instant::now() - reading.taken_at).as_millis()
+        ),
+        Some(None) => println!("No reading yet"),
+        None => {}
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let mut button = Input::new(peripherals.GPIO0, in_config);
+
+    let mut io = Io::new(peripherals.IO_MUX);
+    io.set_interrupt_handler(button_handler);
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    let mut timer = PeriodicTimer::new(timg0.timer0);
+    timer.set_interrupt_handler(timer_handler);
+    timer.enable_interrupt(true);
+    timer
+        .start(Duration::from_millis(CONFIG.sample_interval_ms as u64))
+        .unwrap();
+
+    critical_section::with(|cs| {
+        button.listen(Event::FallingEdge);
+        BUTTON.borrow_ref_mut(cs).replace(button);
+        TIMER.borrow_ref_mut(cs).replace(timer);
+    });
+
+    loop {
+        // Take the flag and clear it in one critical section
+        let due = critical_section::with(|cs| READ_DUE.replace(cs, false));
+
+        if due {
+            match aht20.read(&mut i2c) {
+                Ok(Measurement {
+                    temperature,
+                    humidity,
+                }) => {
+                    let reading = Reading {
+                        temperature,
+                        humidity,
+                        taken_at: Instant::now(),
+                    };
+                    critical_section::with(|cs| {
+                        LATEST.borrow_ref_mut(cs).replace(reading);
+                    });
+                    println!("Stored new reading");
+                }
+                Err(e) => {
+                    println!("Failed to read data from AHT20: {:?}", e);
+                }
+            }
+        }
+
+        delay.delay_millis(10);
+    }
+}
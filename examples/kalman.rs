@@ -0,0 +1,107 @@
+//! Smooths a distance reading with a 1D Kalman filter
+//!
+//! The following wiring is assumed:
+//! - Sharp GP2Y0A21YK0F (10-80cm IR distance sensor) Vo => GPIO3
+//! - Sensor VCC => 5V, GND => GND, with a 10uF capacitor across VCC/GND
+//!
+//! The Sharp sensor's output is noisy and non-linear, a lookup table turns
+//! the voltage into centimeters and `Kalman1d` smooths the result.
+//!
+//! The filter keeps an estimate `x` and its variance `p`. Every update first
+//! grows `p` by `q` (the target may have moved), then blends in the new
+//! measurement weighted by how its variance `r` compares to `p`. Tuning:
+//! - `r` is the measurement noise variance. Measure it: hold the target
+//!   still, log raw readings and take their variance (the sensor here
+//!   jitters by about ±1 cm, so r = 1.0).
+//! - `q` is how much the true distance may change per sample, squared. Small
+//!   `q` trusts the model: very smooth but slow to follow real movement.
+//!   Large `q` trusts the measurements: follows quickly but passes more
+//!   noise. Start at q = r / 100 and raise it until the lag is acceptable.
+//!
+//! Only the ratio q/r matters for the estimate, `p` settles at a steady
+//! value after a few samples and the filter then acts like an EWMA with a
+//! gain derived from that ratio.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{gp2y0a21::millivolts_to_cm, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    main,
+};
+use esp_println::println;
+
+const PROCESS_NOISE: f32 = 0.05; // q, cm² per sample
+const MEASUREMENT_NOISE: f32 = 1.0; // r, cm²
+const SAMPLE_INTERVAL_MS: u32 = 50;
+
+struct Kalman1d {
+    /// Process noise variance
+    q: f32,
+    /// Measurement noise variance
+    r: f32,
+    /// Current estimate
+    x: f32,
+    /// Variance of the current estimate
+    p: f32,
+}
+
+impl Kalman1d {
+    /// Starts from the first measurement with a large variance, so the
+    /// first few updates converge quickly
+    fn new(q: f32, r: f32, initial: f32) -> Self {
+        Kalman1d {
+            q,
+            r,
+            x: initial,
+            p: r * 10.0,
+        }
+    }
+
+    fn update(&mut self, measurement: f32) -> f32 {
+        // Predict: the estimate stays, its uncertainty grows
+        self.p += self.q;
+
+        // Correct: move towards the measurement by the Kalman gain
+        let k = self.p / (self.p + self.r);
+        self.x += k * (measurement - self.x);
+        self.p *= 1.0 - k;
+
+        self.x
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let delay = Delay::new();
+
+    // 12-bit ADC with 11dB attenuation reads roughly 0..2500mV
+    let mut read_cm = || {
+        let raw: u16 = nb::block!(adc1.read_oneshot(&mut pin)).unwrap();
+        millivolts_to_cm(raw as u32 * 2500 / 4095)
+    };
+
+    let mut filter = Kalman1d::new(PROCESS_NOISE, MEASUREMENT_NOISE, read_cm());
+
+    loop {
+        let measured = read_cm();
+        let estimate = filter.update(measured);
+
+        println!(
+            "Measured: {:.1} cm, Kalman: {:.1} cm (variance {:.3})",
+            measured, estimate, filter.p
+        );
+
+        delay.delay_millis(SAMPLE_INTERVAL_MS);
+    }
+}
@@ -0,0 +1,194 @@
+//! Shows AHT20 readings on a 16x2 HD44780 LCD with a PCF8574 I2C backpack
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - LCD backpack and AHT20 on the same bus
+//!
+//! Most backpacks sit at 0x27, the ones with a PCF8574A chip at 0x3F. The
+//! backpack maps its pins as P0 = RS, P1 = RW, P2 = EN, P3 = backlight and
+//! P4..P7 = D4..D7, so the LCD runs in 4-bit mode: every byte is sent as two
+//! nibbles, each latched by a pulse on EN.
+//!
+//! The LCD needs 5V, and most backpacks have their own pull-ups to 5V on
+//! SDA and SCL. The ESP32-C3 GPIOs are not 5V tolerant, so these would put
+//! 5V on GPIO8/GPIO9. Remove the backpack's pull-ups and pull SDA and SCL up
+//! to 3V3 instead, or put a level shifter between the board and the
+//! backpack.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, Error as I2cError, I2c},
+    main,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+use heapless::String;
+
+const LCD_ADDR: u8 = 0x27;
+
+const RS: u8 = 0x01;
+const EN: u8 = 0x04;
+const BACKLIGHT: u8 = 0x08;
+
+const CMD_CLEAR: u8 = 0x01;
+const CMD_ENTRY_MODE: u8 = 0x06; // Increment cursor, no display shift
+const CMD_DISPLAY_ON: u8 = 0x0C; // Display on, cursor off, blink off
+const CMD_FUNCTION_SET: u8 = 0x28; // 4-bit, 2 lines, 5x8 font
+const CMD_SET_DDRAM: u8 = 0x80;
+const DEGREE_SYMBOL: u8 = 0xDF; // In the HD44780 character ROM
+
+struct Lcd {
+    addr: u8,
+    delay: Delay,
+}
+
+impl Lcd {
+    fn new(i2c: &mut I2c<'_, Blocking>, addr: u8) -> Result<Self, I2cError> {
+        let lcd = Lcd {
+            addr,
+            delay: Delay::new(),
+        };
+
+        // Power-on wait, then the datasheet's "initialization by instruction":
+        // three times 8-bit mode to get a known state, then switch to 4-bit
+        lcd.delay.delay_millis(50);
+        lcd.write_nibble(i2c, 0x30, 0)?;
+        lcd.delay.delay_micros(4500);
+        lcd.write_nibble(i2c, 0x30, 0)?;
+        lcd.delay.delay_micros(150);
+        lcd.write_nibble(i2c, 0x30, 0)?;
+        lcd.delay.delay_micros(150);
+        lcd.write_nibble(i2c, 0x20, 0)?;
+
+        lcd.command(i2c, CMD_FUNCTION_SET)?;
+        lcd.command(i2c, CMD_DISPLAY_ON)?;
+        lcd.clear(i2c)?;
+        lcd.command(i2c, CMD_ENTRY_MODE)?;
+
+        Ok(lcd)
+    }
+
+    /// Puts the upper nibble of `nibble` on D4..D7 and pulses EN
+    fn write_nibble(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        nibble: u8,
+        mode: u8,
+    ) -> Result<(), I2cError> {
+        let data = (nibble & 0xF0) | mode | BACKLIGHT;
+        // EN has to be high for at least 450ns, the data is latched on the
+        // falling edge. One I2C byte at 100kHz already takes ~90us.
+        i2c.write(self.addr, &[data | EN])?;
+        i2c.write(self.addr, &[data])?;
+        // Most instructions need 37us to execute
+        self.delay.delay_micros(50);
+        Ok(())
+    }
+
+    fn write_byte(&self, i2c: &mut I2c<'_, Blocking>, byte: u8, mode: u8) -> Result<(), I2cError> {
+        self.write_nibble(i2c, byte & 0xF0, mode)?;
+        self.write_nibble(i2c, byte << 4, mode)
+    }
+
+    fn command(&self, i2c: &mut I2c<'_, Blocking>, cmd: u8) -> Result<(), I2cError> {
+        self.write_byte(i2c, cmd, 0)
+    }
+
+    fn clear(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), I2cError> {
+        self.command(i2c, CMD_CLEAR)?;
+        // Clear is the slow one, up to 1.52ms
+        self.delay.delay_millis(2);
+        Ok(())
+    }
+
+    fn set_cursor(&self, i2c: &mut I2c<'_, Blocking>, col: u8, row: u8) -> Result<(), I2cError> {
+        // Line 2 starts at DDRAM address 0x40
+        let addr = col + if row == 0 { 0x00 } else { 0x40 };
+        self.command(i2c, CMD_SET_DDRAM | addr)
+    }
+
+    fn print(&self, i2c: &mut I2c<'_, Blocking>, bytes: &[u8]) -> Result<(), I2cError> {
+        for &byte in bytes {
+            self.write_byte(i2c, byte, RS)?;
+        }
+        Ok(())
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let lcd = match Lcd::new(&mut i2c, LCD_ADDR) {
+        Ok(lcd) => lcd,
+        Err(e) => {
+            panic!("Failed to initialize LCD at 0x{:02x}: {:?}", LCD_ADDR, e);
+        }
+    };
+    lcd.print(&mut i2c, b"Starting...").ok();
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    loop {
+        let mut line1: String<16> = String::new();
+        let mut line2: String<16> = String::new();
+
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => {
+                write!(line1, "Temp: {:6.1} ", temperature).ok();
+                write!(line2, "Hum:  {:6.1} %", humidity).ok();
+            }
+            Err(e) => {
+                println!("Failed to read data from AHT20: {:?}", e);
+                write!(line1, "Sensor error").ok();
+            }
+        }
+
+        let result = lcd.clear(&mut i2c).and_then(|_| {
+            lcd.set_cursor(&mut i2c, 0, 0)?;
+            lcd.print(&mut i2c, line1.as_bytes())?;
+            if line1.starts_with("Temp") {
+                lcd.print(&mut i2c, &[DEGREE_SYMBOL, b'C'])?;
+            }
+            lcd.set_cursor(&mut i2c, 0, 1)?;
+            lcd.print(&mut i2c, line2.as_bytes())
+        });
+        if let Err(e) = result {
+            println!("Failed to update LCD: {:?}", e);
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
@@ -0,0 +1,211 @@
+//! LED strip controller: a button picks the animation and the brightness
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//! - Button => GPIO0 -> GND
+//!
+//! A short press switches to the next animation (solid, rainbow, fade,
+//! chase), holding the button for `LONG_PRESS` steps through the
+//! brightness levels instead. The long press fires while the button is
+//! still held, so it is clear when to let go, and the release after it is
+//! not counted as a short press.
+//!
+//! The button is polled once per frame and debounced the same way as in
+//! the press_timer example: a level only counts once it held for
+//! `DEBOUNCE`.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Ticker};
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    gpio::{Input, InputConfig, Pull},
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+const FRAME_TIME: Duration = Duration::from_millis(20);
+
+const DEBOUNCE: Duration = Duration::from_millis(20);
+const LONG_PRESS: Duration = Duration::from_millis(600);
+
+const BRIGHTNESS_LEVELS: [u8; 4] = [16, 64, 128, 255];
+
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    Solid,
+    Rainbow,
+    Fade,
+    Chase,
+}
+
+impl Mode {
+    fn next(self) -> Mode {
+        match self {
+            Mode::Solid => Mode::Rainbow,
+            Mode::Rainbow => Mode::Fade,
+            Mode::Fade => Mode::Chase,
+            Mode::Chase => Mode::Solid,
+        }
+    }
+
+    /// Pixel `i` of frame `frame` at full brightness, as (r, g, b, w)
+    fn pixel(self, i: usize, frame: u32) -> (u8, u8, u8, u8) {
+        match self {
+            Mode::Solid => (0, 0, 0, 255),
+            Mode::Rainbow => {
+                let (r, g, b) = wheel(
+                    frame
+                        .wrapping_mul(2)
+                        .wrapping_add(i as u32 * 256 / NUM_LEDS as u32) as u8,
+                );
+                (r, g, b, 0)
+            }
+            Mode::Fade => {
+                // Triangle wave, 0 -> 255 -> 0 over 512 frames
+                let phase = (frame % 512) as i32;
+                let level = (255 - (phase - 255).abs()).clamp(0, 255) as u8;
+                (level, level / 3, 0, 0)
+            }
+            Mode::Chase => {
+                if i == (frame / 4) as usize % NUM_LEDS {
+                    (0, 120, 255, 0)
+                } else {
+                    (0, 0, 0, 0)
+                }
+            }
+        }
+    }
+}
+
+enum Press {
+    Short,
+    Long,
+}
+
+/// Debounced button with short and long press detection
+struct Button<'a> {
+    input: Input<'a>,
+    pressed: bool,
+    changed_at: Option<Instant>,
+    pressed_at: Instant,
+    long_fired: bool,
+}
+
+impl<'a> Button<'a> {
+    fn new(input: Input<'a>) -> Self {
+        Button {
+            input,
+            pressed: false,
+            changed_at: None,
+            pressed_at: Instant::now(),
+            long_fired: false,
+        }
+    }
+
+    /// Call regularly, returns a press once it is known which kind it was
+    fn poll(&mut self) -> Option<Press> {
+        let now = Instant::now();
+        let raw_pressed = self.input.is_low();
+
+        if raw_pressed == self.pressed {
+            // Bounced back before it was stable, not a real edge
+            self.changed_at = None;
+        } else {
+            let since = *self.changed_at.get_or_insert(now);
+            if now - since >= DEBOUNCE {
+                self.pressed = raw_pressed;
+                self.changed_at = None;
+
+                if self.pressed {
+                    self.pressed_at = since;
+                    self.long_fired = false;
+                } else if !self.long_fired {
+                    return Some(Press::Short);
+                }
+            }
+        }
+
+        if self.pressed && !self.long_fired && now - self.pressed_at >= LONG_PRESS {
+            self.long_fired = true;
+            return Some(Press::Long);
+        }
+        None
+    }
+}
+
+/// Color wheel: 0..=255 goes red -> green -> blue -> red
+fn wheel(pos: u8) -> (u8, u8, u8) {
+    match pos {
+        0..=84 => (255 - pos * 3, pos * 3, 0),
+        85..=169 => {
+            let pos = pos - 85;
+            (0, 255 - pos * 3, pos * 3)
+        }
+        _ => {
+            let pos = pos - 170;
+            (pos * 3, 0, 255 - pos * 3)
+        }
+    }
+}
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let mut button = Button::new(Input::new(peripherals.GPIO0, in_config));
+
+    let mut mode = Mode::Solid;
+    let mut level = 1;
+    let mut frame = 0u32;
+    let mut pixels = [(0, 0, 0, 0); NUM_LEDS];
+    let mut ticker = Ticker::every(FRAME_TIME);
+
+    println!("Mode: {:?}, brightness: {}", mode, BRIGHTNESS_LEVELS[level]);
+
+    loop {
+        match button.poll() {
+            Some(Press::Short) => {
+                mode = mode.next();
+                println!("Mode: {:?}", mode);
+            }
+            Some(Press::Long) => {
+                level = (level + 1) % BRIGHTNESS_LEVELS.len();
+                println!("Brightness: {}", BRIGHTNESS_LEVELS[level]);
+            }
+            None => {}
+        }
+
+        let brightness = BRIGHTNESS_LEVELS[level];
+        for (i, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = sk6812::scale(mode.pixel(i, frame), brightness);
+        }
+        strip.flush(&pixels).await;
+
+        frame = frame.wrapping_add(1);
+        ticker.next().await;
+    }
+}
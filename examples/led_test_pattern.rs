@@ -0,0 +1,67 @@
+//! Test pattern for bringing up a new sk6812 RGBW LED strip
+//!
+//! Connect a sk6812 RGBW LED strip to GPIO4.
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//!
+//! First pixels 0, 1 and 2 light up red, green and blue. If the colors come
+//! out in a different order, the strip expects another byte order. Then a
+//! single white pixel walks from the data input to the end of the strip,
+//! count the steps to verify NUM_LEDS and the wiring direction.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    loop {
+        println!("Color order: pixel 0 red, pixel 1 green, pixel 2 blue");
+        let mut pixels = [(0, 0, 0, 0); NUM_LEDS];
+        pixels[0] = (20, 0, 0, 0);
+        pixels[1] = (0, 20, 0, 0);
+        pixels[2] = (0, 0, 20, 0);
+        strip.flush(&pixels).await;
+        Timer::after(Duration::from_secs(3)).await;
+
+        println!("Walking white pixel over {} LEDs", NUM_LEDS);
+        for i in 0..NUM_LEDS {
+            let mut pixels = [(0, 0, 0, 0); NUM_LEDS];
+            pixels[i] = (0, 0, 0, 20);
+            println!("Pixel {}", i);
+            strip.flush(&pixels).await;
+            Timer::after(Duration::from_millis(300)).await;
+        }
+    }
+}
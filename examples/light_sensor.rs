@@ -0,0 +1,58 @@
+//! Reads ambient light from a BH1750
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - ADDR => GND (address 0x23, tie it to 3V3 for 0x5C)
+//!
+//! Every reading uses the one-time high resolution mode: the sensor wakes
+//! up, measures once with 1 lx resolution and powers down again on its own,
+//! so it only draws current while measuring.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{bh1750::Bh1750, config::CONFIG, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let bh1750 = Bh1750::new(delay);
+    match bh1750.start(&mut i2c) {
+        Ok(_) => println!("BH1750 sensor initialized!"),
+        Err(e) => {
+            panic!("Failed to initialize BH1750: {:?}", e);
+        }
+    }
+
+    loop {
+        match bh1750.read_lux(&mut i2c) {
+            Ok(lux) => println!("Illuminance: {:.1} lx", lux),
+            Err(e) => println!("Failed to read data from BH1750: {:?}", e),
+        }
+
+        delay.delay_millis(1000);
+    }
+}
@@ -0,0 +1,151 @@
+//! Scrollable menu navigated with a rotary encoder, rendered over serial
+//!
+//! The following wiring is assumed:
+//! - Encoder CLK (A) => GPIO2
+//! - Encoder DT (B) => GPIO3
+//! - Encoder SW (push button) => GPIO0 -> GND
+//!
+//! Turning the knob moves the selection (wrapping around at both ends),
+//! pressing it selects the current entry. Both inputs are handled in the
+//! GPIO interrupt and debounced there, the main loop only renders.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Event, Input, InputConfig, Io, Pull},
+    handler, main, time,
+};
+use esp_println::println;
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+const ENCODER_DEBOUNCE_MS: u64 = 5;
+const BUTTON_DEBOUNCE_MS: u64 = 200;
+
+struct Inputs {
+    clk: Input<'static>,
+    dt: Input<'static>,
+    sw: Input<'static>,
+    last_turn: u64,
+    last_press: u64,
+}
+
+// global mutable state shared with the interrupt handler
+static INPUTS: Mutex<RefCell<Option<Inputs>>> = Mutex::new(RefCell::new(None));
+static STEPS: Mutex<RefCell<i32>> = Mutex::new(RefCell::new(0));
+static PRESSED: Mutex<RefCell<bool>> = Mutex::new(RefCell::new(false));
+
+#[handler]
+fn handler() {
+    critical_section::with(|cs| {
+        let mut inputs = INPUTS.borrow_ref_mut(cs);
+        let Some(inputs) = inputs.as_mut() else {
+            return;
+        };
+        let now = time::Instant::now().duration_since_epoch().as_millis();
+
+        if inputs.clk.is_interrupt_set() {
+            inputs.clk.clear_interrupt();
+            if now - inputs.last_turn > ENCODER_DEBOUNCE_MS {
+                inputs.last_turn = now;
+                // On the falling edge of A, B tells the direction
+                let mut steps = STEPS.borrow_ref_mut(cs);
+                if inputs.dt.is_high() {
+                    *steps += 1;
+                } else {
+                    *steps -= 1;
+                }
+            }
+        }
+
+        if inputs.sw.is_interrupt_set() {
+            inputs.sw.clear_interrupt();
+            if now - inputs.last_press > BUTTON_DEBOUNCE_MS {
+                inputs.last_press = now;
+                *PRESSED.borrow_ref_mut(cs) = true;
+            }
+        }
+    });
+}
+
+struct Menu<'a> {
+    items: &'a [&'a str],
+    selected: usize,
+}
+
+impl<'a> Menu<'a> {
+    fn new(items: &'a [&'a str]) -> Self {
+        Menu { items, selected: 0 }
+    }
+
+    /// Moves the selection by `steps`, wrapping around at both ends
+    fn scroll(&mut self, steps: i32) {
+        let len = self.items.len() as i32;
+        self.selected = (self.selected as i32 + steps).rem_euclid(len) as usize;
+    }
+
+    fn selected(&self) -> &str {
+        self.items[self.selected]
+    }
+
+    fn render(&self) {
+        println!("----------------");
+        for (i, item) in self.items.iter().enumerate() {
+            let marker = if i == self.selected { ">" } else { " " };
+            println!("{} {}", marker, item);
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let in_config = InputConfig::default().with_pull(Pull::Up);
+    let mut clk = Input::new(peripherals.GPIO2, in_config);
+    let dt = Input::new(peripherals.GPIO3, in_config);
+    let mut sw = Input::new(peripherals.GPIO0, in_config);
+
+    let mut io = Io::new(peripherals.IO_MUX);
+    io.set_interrupt_handler(handler);
+
+    critical_section::with(|cs| {
+        clk.listen(Event::FallingEdge);
+        sw.listen(Event::FallingEdge);
+        INPUTS.borrow_ref_mut(cs).replace(Inputs {
+            clk,
+            dt,
+            sw,
+            last_turn: 0,
+            last_press: 0,
+        });
+    });
+
+    let delay = Delay::new();
+
+    let items = ["Show temperature", "LED on", "LED off", "Settings", "About"];
+    let mut menu = Menu::new(&items);
+    menu.render();
+
+    loop {
+        let (steps, pressed) =
+            critical_section::with(|cs| (STEPS.replace(cs, 0), PRESSED.replace(cs, false)));
+
+        if steps != 0 {
+            menu.scroll(steps);
+            menu.render();
+        }
+
+        if pressed {
+            println!("Selected: {}", menu.selected());
+        }
+
+        delay.delay_millis(20);
+    }
+}
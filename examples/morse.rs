@@ -0,0 +1,88 @@
+//! Blinks text as Morse code on the onboard LED
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8 (the onboard LED, lit when the pin is low)
+//!
+//! Timing follows the international standard, all in units of one dot:
+//! dash = 3, gap between the elements of a letter = 1, between letters = 3,
+//! between words = 7. At `WPM` words per minute one unit is 1200 / WPM ms
+//! (the "PARIS" convention). Letters, digits and spaces are sent, other
+//! characters are skipped; lower case is sent as upper case.
+//!
+//! Handy as a status signal on a board without a serial console attached.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+};
+use esp_println::println;
+
+const WPM: u32 = 12;
+const UNIT_MS: u32 = 1200 / WPM;
+
+const MESSAGE: &str = "Hello from ESP32 C3";
+
+// A..Z then 0..9
+const LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+const DIGITS: [&str; 10] = [
+    "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+];
+
+fn encode(c: char) -> Option<&'static str> {
+    match c.to_ascii_uppercase() {
+        c @ 'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+        c @ '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+        _ => None,
+    }
+}
+
+/// Blinks `text` in Morse code, blocking until it is sent. The LED is
+/// assumed active low like the onboard one and is left off.
+fn send_morse(led: &mut Output, delay: &Delay, text: &str) {
+    // Gap still owed before the next element, so trailing gaps are not waited out
+    let mut gap = 0;
+    for c in text.chars() {
+        if c == ' ' {
+            gap = 7;
+            continue;
+        }
+        let Some(code) = encode(c) else {
+            continue;
+        };
+
+        for element in code.chars() {
+            delay.delay_millis(gap * UNIT_MS);
+            led.set_low();
+            delay.delay_millis(if element == '-' { 3 } else { 1 } * UNIT_MS);
+            led.set_high();
+            gap = 1;
+        }
+        gap = 3;
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = OutputConfig::default();
+    let mut led = Output::new(peripherals.GPIO8, Level::High, config);
+
+    let delay = Delay::new();
+
+    loop {
+        println!("Sending \"{}\" at {} WPM", MESSAGE, WPM);
+        send_morse(&mut led, &delay, MESSAGE);
+        delay.delay_millis(3000);
+    }
+}
@@ -0,0 +1,159 @@
+//! Handles interrupts from several GPIOs in one handler
+//!
+//! The following wiring is assumed:
+//! - Button A => GPIO0 -> GND
+//! - Button B => GPIO1 -> GND
+//! - sensor data-ready (DRDY/INT) output => GPIO10, active high
+//!
+//! All GPIO interrupts on the C3 arrive at the same interrupt, so there is
+//! a single handler for the whole IO_MUX. It asks each pin whether it
+//! triggered with `is_interrupt_set()`, handles that pin and clears its
+//! flag. Several pins can be flagged in the same call, so every pin is
+//! checked, not just the first match. A flag left set makes the interrupt
+//! fire again right after the handler returns, forever.
+//!
+//! Every pin lives in its own `Mutex<RefCell<..>>` slot together with its
+//! state, so adding a source means adding a slot and one more check in the
+//! handler, without touching the others. The main loop only reads the
+//! counters.
+//!
+//! The handler runs at `Priority1`, the default. A higher priority is set
+//! on the attribute, e.g. `#[handler(priority = Priority::Priority3)]`,
+//! and lets this handler preempt lower priority ones (timers, UART, ...).
+//! Keep it short either way: it runs with the critical section held.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Event, Input, InputConfig, Io, Pull},
+    handler,
+    interrupt::Priority,
+    main, time,
+};
+use esp_println::println;
+
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+const DEBOUNCE_MS: u64 = 200;
+
+struct Button {
+    input: Input<'static>,
+    presses: u32,
+    last_press: u64,
+}
+
+struct DataReady {
+    input: Input<'static>,
+    count: u32,
+}
+
+// One slot per interrupt source
+static BUTTON_A: Mutex<RefCell<Option<Button>>> = Mutex::new(RefCell::new(None));
+static BUTTON_B: Mutex<RefCell<Option<Button>>> = Mutex::new(RefCell::new(None));
+static DATA_READY: Mutex<RefCell<Option<DataReady>>> = Mutex::new(RefCell::new(None));
+
+/// Counts a debounced press if this button triggered
+fn check_button(
+    slot: &Mutex<RefCell<Option<Button>>>,
+    cs: critical_section::CriticalSection,
+    now: u64,
+) {
+    let mut button = slot.borrow_ref_mut(cs);
+    let Some(button) = button.as_mut() else {
+        return;
+    };
+    if button.input.is_interrupt_set() {
+        button.input.clear_interrupt();
+        if now - button.last_press > DEBOUNCE_MS {
+            button.last_press = now;
+            button.presses += 1;
+        }
+    }
+}
+
+#[handler(priority = Priority::Priority1)]
+fn handler() {
+    critical_section::with(|cs| {
+        let now = time::Instant::now().duration_since_epoch().as_millis();
+
+        check_button(&BUTTON_A, cs, now);
+        check_button(&BUTTON_B, cs, now);
+
+        let mut data_ready = DATA_READY.borrow_ref_mut(cs);
+        if let Some(data_ready) = data_ready.as_mut() {
+            if data_ready.input.is_interrupt_set() {
+                data_ready.input.clear_interrupt();
+                // A real driver would fetch the sample here or flag the main loop to
+                data_ready.count += 1;
+            }
+        }
+    });
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let button_config = InputConfig::default().with_pull(Pull::Up);
+    let mut button_a = Input::new(peripherals.GPIO0, button_config);
+    let mut button_b = Input::new(peripherals.GPIO1, button_config);
+    let mut drdy = Input::new(
+        peripherals.GPIO10,
+        InputConfig::default().with_pull(Pull::Down),
+    );
+
+    let mut io = Io::new(peripherals.IO_MUX);
+    io.set_interrupt_handler(handler);
+
+    critical_section::with(|cs| {
+        button_a.listen(Event::FallingEdge);
+        button_b.listen(Event::FallingEdge);
+        drdy.listen(Event::RisingEdge);
+        BUTTON_A.borrow_ref_mut(cs).replace(Button {
+            input: button_a,
+            presses: 0,
+            last_press: 0,
+        });
+        BUTTON_B.borrow_ref_mut(cs).replace(Button {
+            input: button_b,
+            presses: 0,
+            last_press: 0,
+        });
+        DATA_READY.borrow_ref_mut(cs).replace(DataReady {
+            input: drdy,
+            count: 0,
+        });
+    });
+
+    let delay = Delay::new();
+    let mut last = (0, 0, 0);
+
+    loop {
+        let counts = critical_section::with(|cs| {
+            let presses = |slot: &Mutex<RefCell<Option<Button>>>| {
+                slot.borrow_ref(cs).as_ref().map_or(0, |b| b.presses)
+            };
+            (
+                presses(&BUTTON_A),
+                presses(&BUTTON_B),
+                DATA_READY.borrow_ref(cs).as_ref().map_or(0, |d| d.count),
+            )
+        });
+
+        if counts != last {
+            println!(
+                "Button A: {}, button B: {}, data ready: {}",
+                counts.0, counts.1, counts.2
+            );
+            last = counts;
+        }
+
+        delay.delay_millis(50);
+    }
+}
@@ -10,6 +10,7 @@
 
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::print_banner;
 use esp_backtrace as _;
 use esp_hal::{
     gpio::Level,
@@ -48,6 +49,7 @@ fn create_led_bits(r: u8, g: u8, b: u8) -> [u32; 25] {
 #[esp_hal_embassy::main]
 async fn main(_spawner: Spawner) {
     let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
     let timg0 = TimerGroup::new(peripherals.TIMG0);
     esp_hal_embassy::init(timg0.timer0);
 
@@ -0,0 +1,148 @@
+//! Holds a temperature setpoint with a PID controller driving a heater
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - Heater driver (MOSFET gate) => GPIO3
+//!
+//! The AHT20 measures the temperature, the controller output (0..100 %) is
+//! the PWM duty of the heater. For a fan (cooling) swap the arguments of
+//! `Pid::update`, so a temperature above the setpoint gives a positive error.
+//!
+//! The controller works in f32: gains are per °C, `dt` is in seconds and
+//! the output is in percent of duty.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    ledc::{
+        channel::{self, ChannelIFace},
+        timer::{self, TimerIFace},
+        LSGlobalClkSource, Ledc, LowSpeed,
+    },
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const SETPOINT: f32 = 30.0; // °C
+const PERIOD_MS: u32 = 1000;
+
+struct Pid {
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    out_min: f32,
+    out_max: f32,
+    integral: f32,
+    prev_error: Option<f32>,
+}
+
+impl Pid {
+    fn new(kp: f32, ki: f32, kd: f32, out_min: f32, out_max: f32) -> Self {
+        Pid {
+            kp,
+            ki,
+            kd,
+            out_min,
+            out_max,
+            integral: 0.0,
+            prev_error: None,
+        }
+    }
+
+    /// Returns the output clamped to `out_min..=out_max`. The integral only
+    /// grows while the output is not saturated (anti-windup), so it does not
+    /// keep accumulating while the heater is already at 100 %.
+    fn update(&mut self, setpoint: f32, measured: f32, dt: f32) -> f32 {
+        let error = setpoint - measured;
+        let derivative = match self.prev_error {
+            Some(prev) => (error - prev) / dt,
+            None => 0.0,
+        };
+        self.prev_error = Some(error);
+
+        let integral = self.integral + error * dt;
+        let output = self.kp * error + self.ki * integral + self.kd * derivative;
+
+        if (self.out_min..=self.out_max).contains(&output) {
+            self.integral = integral;
+        }
+
+        output.clamp(self.out_min, self.out_max)
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: Rate::from_khz(1),
+        })
+        .unwrap();
+    let mut heater = ledc.channel(channel::Number::Channel0, peripherals.GPIO3);
+    heater
+        .configure(channel::config::Config {
+            timer: &lstimer0,
+            duty_pct: 0,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
+
+    let mut pid = Pid::new(8.0, 0.2, 20.0, 0.0, 100.0);
+    let dt = PERIOD_MS as f32 / 1000.0;
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement { temperature, .. }) => {
+                let duty = pid.update(SETPOINT, temperature, dt);
+                heater.set_duty(duty as u8).unwrap();
+                println!(
+                    "Setpoint: {:.1} °C, Temperature: {:.2} °C, Heater: {:.0} %",
+                    SETPOINT, temperature, duty
+                );
+            }
+            Err(e) => {
+                // Never leave the heater on without feedback
+                heater.set_duty(0).unwrap();
+                println!("Failed to read data from AHT20: {:?}, heater off", e);
+            }
+        }
+
+        delay.delay_millis(PERIOD_MS);
+    }
+}
@@ -0,0 +1,96 @@
+//! Catches two subsystems wired to the same GPIO with a clear message
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//! - external LED => GPIO3 (only with `USE_ONBOARD_LED = false`)
+//!
+//! The blink example drives the onboard LED on GPIO8, the sensor examples
+//! use GPIO8 as I2C SDA. Merging the two fails, and not obviously why: with
+//! fixed pins it is a "use of moved value" compile error, with pins picked
+//! by number (as here, from a table of `AnyPin`s) it is an `unwrap` on
+//! `None` somewhere in setup. Each subsystem here claims its pins through
+//! `board::claim_pin` before taking them, so with `USE_ONBOARD_LED = true`
+//! setup stops with "GPIO8 is used by both status LED and I2C SDA". Set it
+//! to false to move the LED to GPIO3 and everything runs.
+//!
+//! The I2C pins are also checked at compile time with `board::pins_unique`.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{aht20::Aht20, board, config::CONFIG, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{AnyPin, Level, Output, OutputConfig, Pin},
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const USE_ONBOARD_LED: bool = true;
+const EXTERNAL_LED: u8 = 3;
+
+// Fails the build if someone points SDA and SCL at the same pin
+const _: () = assert!(board::pins_unique(&[board::I2C_SDA, board::I2C_SCL]));
+
+/// Claims `gpio` for `owner` and takes it out of the pin table
+fn take_pin(pins: &mut [Option<AnyPin>], gpio: u8, owner: &'static str) -> AnyPin {
+    board::claim_pin(gpio, owner);
+    pins[gpio as usize]
+        .take()
+        .unwrap_or_else(|| panic!("GPIO{} is not in the pin table", gpio))
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    // Pins this example may use, looked up by GPIO number
+    let mut pins: [Option<AnyPin>; board::NUM_GPIOS] = core::array::from_fn(|_| None);
+    pins[3] = Some(peripherals.GPIO3.degrade());
+    pins[8] = Some(peripherals.GPIO8.degrade());
+    pins[9] = Some(peripherals.GPIO9.degrade());
+
+    // Status LED
+    let led_pin = if USE_ONBOARD_LED {
+        board::LED
+    } else {
+        EXTERNAL_LED
+    };
+    let mut led = Output::new(
+        take_pin(&mut pins, led_pin, "status LED"),
+        Level::High,
+        OutputConfig::default(),
+    );
+
+    // Sensor bus
+    let sda = take_pin(&mut pins, board::I2C_SDA, "I2C SDA");
+    let scl = take_pin(&mut pins, board::I2C_SCL, "I2C SCL");
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(sda)
+    .with_scl(scl);
+
+    println!("Pin setup OK");
+
+    let delay = Delay::new();
+    let aht20 = Aht20::new(delay);
+
+    loop {
+        led.toggle();
+        match aht20.status(&mut i2c) {
+            Ok(status) => println!("AHT20 status: 0x{:02x}", status),
+            Err(e) => println!("Failed to read AHT20 status: {:?}", e),
+        }
+        delay.delay_millis(1000);
+    }
+}
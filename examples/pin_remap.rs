@@ -0,0 +1,73 @@
+//! Reads an AHT20 with I2C routed to non-default pins
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO6
+//! - SCL => GPIO7
+//!
+//! The C3 has no fixed I2C pins: `with_sda`/`with_scl` route the I2C0
+//! signals through the GPIO matrix to whatever pin is passed in. The same
+//! goes for UART, SPI, LEDC and RMT signals. Pins to stay away from:
+//! - GPIO12..GPIO17 are wired to the SPI flash (and GPIO11 is its supply)
+//! - GPIO18/GPIO19 are the USB D-/D+ lines, using them kills the USB serial
+//! - GPIO20/GPIO21 are UART0 RX/TX, the console if USB is not used
+//! - GPIO2, GPIO8 and GPIO9 are strapping pins, external pull-downs on them
+//!   can prevent the chip from booting (GPIO8 also drives the onboard LED)
+//!
+//! On the super mini that leaves GPIO0..GPIO7 and GPIO10 as the safe choices.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    // Any free GPIO works here, not just GPIO8/GPIO9
+    .with_sda(peripherals.GPIO6)
+    .with_scl(peripherals.GPIO7);
+
+    let aht20 = Aht20::new(delay);
+    match aht20.start(&mut i2c) {
+        Ok(_) => println!("AHT20 found on SDA=GPIO6, SCL=GPIO7"),
+        Err(e) => panic!("Failed to initialize AHT20 on remapped pins: {:?}", e),
+    }
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => println!(
+                "Temperature: {:.2} °C, Humidity: {:.2} %",
+                temperature, humidity
+            ),
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
@@ -0,0 +1,72 @@
+//! Streams AHT20 readings in a serial-plotter friendly line format
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! Every data line looks like `>23.41,45.12`: a `>` marker followed by
+//! comma-separated values (temperature °C, humidity %) and a newline.
+//! Anything else (startup messages, errors) has no marker, so the host
+//! side can keep only lines starting with `>` and split them on commas.
+//!
+//! Host side, for example:
+//! - Arduino IDE Serial Plotter or similar tools: strip the `>` and feed
+//!   the rest, each comma-separated column becomes one trace
+//! - Python: `for line in serial.Serial(port, 115200):` keep the lines that
+//!   start with `b">"`, then `map(float, line[1:].split(b","))` and plot
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const SAMPLE_INTERVAL_MS: u32 = 500;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    println!("# columns: temperature_c,humidity_pct");
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => println!(">{:.2},{:.2}", temperature, humidity),
+            Err(e) => println!("# Failed to read data from AHT20: {:?}", e),
+        }
+
+        delay.delay_millis(SAMPLE_INTERVAL_MS);
+    }
+}
@@ -0,0 +1,150 @@
+//! Reads PM1.0, PM2.5 and PM10 from a PMS5003 particulate sensor over UART
+//!
+//! The following wiring is assumed:
+//! - PMS5003 TX (pin 5) => GPIO6 (UART1 RX)
+//! - PMS5003 RX (pin 4) => GPIO5 (UART1 TX), not needed for reading
+//! - PMS5003 VCC (pin 1) => 5V, GND (pin 2) => GND, SET and RESET left open
+//!
+//! The sensor sends a 32 byte frame about once a second at 9600 baud:
+//! - 0x42 0x4D start bytes
+//! - frame length, always 28 (the bytes after it)
+//! - 13 big-endian u16 values, the first six are PM concentrations
+//! - u16 checksum, the sum of all bytes before it
+//!
+//! `FrameParser` takes one byte at a time, so it does not matter where in a
+//! frame reading starts or how the bytes arrive. It waits for the start
+//! bytes, checks the length before collecting anything and throws away a
+//! frame whose checksum does not match, then starts looking for the next
+//! start bytes. A lone 0x42 inside the data can fake a start, the length
+//! and checksum checks catch that.
+//!
+//! The fan needs about 30 s after power-up before the readings settle.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    main,
+    uart::{Config as UartConfig, Uart},
+};
+use esp_println::println;
+
+const START_1: u8 = 0x42;
+const START_2: u8 = 0x4D;
+const FRAME_LEN: usize = 32;
+// Bytes after the length field
+const PAYLOAD_LEN: u16 = 28;
+
+#[derive(Debug)]
+struct Reading {
+    /// µg/m³ under atmospheric conditions, the values to report
+    pm1_0: u16,
+    pm2_5: u16,
+    pm10: u16,
+}
+
+#[derive(Debug)]
+enum FrameError {
+    Length(u16),
+    Checksum { expected: u16, received: u16 },
+}
+
+struct FrameParser {
+    frame: [u8; FRAME_LEN],
+    len: usize,
+}
+
+impl FrameParser {
+    fn new() -> Self {
+        FrameParser {
+            frame: [0; FRAME_LEN],
+            len: 0,
+        }
+    }
+
+    fn word(&self, i: usize) -> u16 {
+        u16::from_be_bytes([self.frame[i], self.frame[i + 1]])
+    }
+
+    /// Feeds one received byte, returns a result once a frame is complete
+    fn feed(&mut self, byte: u8) -> Option<Result<Reading, FrameError>> {
+        match self.len {
+            0 if byte != START_1 => return None,
+            1 if byte != START_2 => {
+                // Maybe this byte starts the real frame
+                self.len = (byte == START_1) as usize;
+                self.frame[0] = byte;
+                return None;
+            }
+            _ => {}
+        }
+
+        self.frame[self.len] = byte;
+        self.len += 1;
+
+        if self.len == 4 && self.word(2) != PAYLOAD_LEN {
+            self.len = 0;
+            return Some(Err(FrameError::Length(self.word(2))));
+        }
+        if self.len < FRAME_LEN {
+            return None;
+        }
+
+        self.len = 0;
+        let expected = self.frame[..FRAME_LEN - 2]
+            .iter()
+            .fold(0u16, |sum, &b| sum.wrapping_add(b as u16));
+        let received = self.word(FRAME_LEN - 2);
+        if expected != received {
+            return Some(Err(FrameError::Checksum { expected, received }));
+        }
+
+        // Words 4..10 are the "CF=1" factory values, 10..16 atmospheric
+        Some(Ok(Reading {
+            pm1_0: self.word(10),
+            pm2_5: self.word(12),
+            pm10: self.word(14),
+        }))
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = UartConfig::default().with_baudrate(9600);
+    let mut uart1 = match Uart::new(peripherals.UART1, config) {
+        Ok(uart) => uart,
+        Err(e) => {
+            panic!("Failed to initialize UART1: {:?}", e);
+        }
+    }
+    .with_tx(peripherals.GPIO5)
+    .with_rx(peripherals.GPIO6);
+
+    let mut parser = FrameParser::new();
+
+    println!("Waiting for PMS5003 frames");
+
+    loop {
+        let byte = match nb::block!(uart1.read_byte()) {
+            Ok(byte) => byte,
+            Err(e) => {
+                println!("UART error: {:?}", e);
+                continue;
+            }
+        };
+
+        match parser.feed(byte) {
+            Some(Ok(reading)) => println!(
+                "PM1.0: {} µg/m³, PM2.5: {} µg/m³, PM10: {} µg/m³",
+                reading.pm1_0, reading.pm2_5, reading.pm10
+            ),
+            Some(Err(e)) => println!("Dropped PMS5003 frame: {:?}", e),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Sets a sk6812 RGBW LED strip's brightness with a potentiometer
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//! - potentiometer ends => 3V3 and GND, wiper => GPIO3
+//!
+//! Every frame the wiper voltage is read `OVERSAMPLING` times and averaged
+//! (see the adc_calibration example), then smoothed towards the previous
+//! value, so ADC jitter does not show up as the strip shimmering while the
+//! knob is still. The knob position maps onto brightness through a square
+//! curve, because the eye sees linear steps at the low end as big jumps and
+//! the upper half of a linear knob as barely changing.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::{adc, config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+const FRAME_TIME: Duration = Duration::from_millis(20);
+
+const OVERSAMPLING: u32 = 16;
+// Fraction of the remaining distance to the new reading covered per frame
+const SMOOTHING: f32 = 0.2;
+// Highest raw value the wiper reaches at 11dB attenuation
+const ADC_MAX: f32 = 4095.0;
+// Only report changes of at least this many brightness steps
+const REPORT_STEP: i16 = 4;
+
+// Color at full brightness, scaled down by the current brightness
+const BASE_COLOR: (u8, u8, u8, u8) = (255, 120, 40, 60);
+
+/// Knob position (0.0..=1.0) to brightness (0..=255)
+fn brightness_for(position: f32) -> u8 {
+    let position = position.clamp(0.0, 1.0);
+    (position * position * 255.0) as u8
+}
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let mut read_position = || {
+        let raw = adc::read_oversampled(
+            || nb::block!(adc1.read_oneshot(&mut pin)).unwrap(),
+            OVERSAMPLING,
+        );
+        raw as f32 / ADC_MAX
+    };
+
+    let mut position = read_position();
+    // Out of range so the first frame is always reported
+    let mut reported: i16 = -REPORT_STEP;
+
+    loop {
+        position += (read_position() - position) * SMOOTHING;
+        let brightness = brightness_for(position);
+
+        if (brightness as i16 - reported).abs() >= REPORT_STEP {
+            println!(
+                "Knob: {:.0} %, brightness: {}",
+                position * 100.0,
+                brightness
+            );
+            reported = brightness as i16;
+        }
+
+        let pixel = sk6812::scale(BASE_COLOR, brightness);
+        strip.flush(&[pixel; NUM_LEDS]).await;
+
+        Timer::after(FRAME_TIME).await;
+    }
+}
@@ -0,0 +1,66 @@
+//! Measures how long each button press lasts
+//!
+//! The following wiring is assumed:
+//! - Button => GPIO0 -> GND
+//!
+//! A level change only counts once the pin has stayed at the new level for
+//! `DEBOUNCE_MS`, so contact bounce never registers as extra presses. The
+//! press and release are timestamped at the moment the level first changed,
+//! not when the debounce accepted it. Both edges are delayed by the same
+//! debounce time anyway, this way the reported duration does not include it.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Pull},
+    main,
+    time::Instant,
+};
+use esp_println::println;
+
+const DEBOUNCE_MS: u64 = 20;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let button = Input::new(peripherals.GPIO0, in_config);
+
+    let delay = Delay::new();
+
+    let mut pressed = false;
+    // When the raw level started to differ from the debounced state
+    let mut changed_at: Option<Instant> = None;
+    let mut pressed_at = Instant::now();
+
+    println!("Press the button, durations are printed on release");
+
+    loop {
+        let raw_pressed = button.is_low();
+
+        if raw_pressed == pressed {
+            // Bounced back before it was stable, not a real edge
+            changed_at = None;
+        } else {
+            let since = *changed_at.get_or_insert_with(Instant::now);
+            if (Instant::now() - since).as_millis() >= DEBOUNCE_MS {
+                pressed = raw_pressed;
+                changed_at = None;
+
+                if pressed {
+                    pressed_at = since;
+                } else {
+                    println!("Press lasted {} ms", (since - pressed_at).as_millis());
+                }
+            }
+        }
+
+        delay.delay_millis(1);
+    }
+}
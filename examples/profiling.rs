@@ -0,0 +1,145 @@
+//! Times sections of a sensor read with the CPU cycle counter
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! `measure` runs a closure and returns how many CPU cycles it took and the
+//! same in microseconds. The ESP32-C3 has no standard `mcycle` CSR, it
+//! counts cycles in its own performance counter instead: `mpcer` (0x7E0)
+//! selects what to count, `mpcmr` (0x7E1) enables it and `mpccr` (0x7E2)
+//! holds the count. The counter is 32 bits wide, at 160 MHz it wraps after
+//! about 26 s, plenty for timing a code block.
+//!
+//! The example splits an AHT20 read into the I2C transfers and the
+//! conversion of the raw data, once with the driver's float `decode` (which
+//! also checks the CRC) and once in fixed point. The C3 has no FPU, so
+//! every float operation is a software routine, yet both conversions are
+//! still tiny next to the bus transfers at 100 kHz.
+//! The cost of `measure` itself is measured first and subtracted.
+
+#![no_std]
+#![no_main]
+
+use core::{arch::asm, hint::black_box};
+use esp32_c3_super_mini_rust::{
+    aht20::{self, Aht20},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    clock::CpuClock,
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const CPU_MHZ: u32 = 160;
+
+/// Starts the performance counter counting CPU cycles
+fn enable_cycle_counter() {
+    unsafe {
+        // mpcer: count clock cycles, mpcmr: counting enabled
+        asm!("csrw 0x7e0, {0}", in(reg) 1u32);
+        asm!("csrw 0x7e1, {0}", in(reg) 1u32);
+    }
+}
+
+fn cycles() -> u32 {
+    let count: u32;
+    unsafe {
+        asm!("csrr {0}, 0x7e2", out(reg) count);
+    }
+    count
+}
+
+/// Runs `f` and returns (cycles, microseconds) it took
+fn measure(f: impl FnOnce()) -> (u32, u32) {
+    let start = cycles();
+    f();
+    let elapsed = cycles().wrapping_sub(start);
+    (elapsed, elapsed / CPU_MHZ)
+}
+
+/// Same conversion in integers: hundredths of a °C and of a %
+fn convert_fixed(buffer: &[u8; 7]) -> (i32, u32) {
+    let humidity_raw =
+        ((buffer[1] as u32) << 12) | ((buffer[2] as u32) << 4) | ((buffer[3] as u32) >> 4);
+    let temp_raw =
+        ((buffer[3] as u32 & 0x0F) << 16) | ((buffer[4] as u32) << 8) | (buffer[5] as u32);
+    // 20 bits times 20000 needs more than 32
+    (
+        ((temp_raw as u64 * 20000) >> 20) as i32 - 5000,
+        ((humidity_raw as u64 * 10000) >> 20) as u32,
+    )
+}
+
+fn report(name: &str, (cycles, us): (u32, u32), overhead: u32) {
+    let cycles = cycles.saturating_sub(overhead);
+    println!("{:<22} {:>9} cycles {:>7} us", name, cycles, us);
+}
+
+#[main]
+fn main() -> ! {
+    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::_160MHz);
+    let peripherals = esp_hal::init(config);
+    print_banner!();
+
+    enable_cycle_counter();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let delay = Delay::new();
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    loop {
+        let (overhead, _) = measure(|| {});
+
+        let mut buffer = [0u8; 7];
+        let trigger = measure(|| {
+            aht20.trigger(&mut i2c).unwrap();
+        });
+        delay.delay_millis(aht20::MEASUREMENT_TIME_MS);
+        let transfer = measure(|| {
+            aht20.fetch_raw(&mut i2c, &mut buffer).unwrap();
+        });
+
+        let mut float = None;
+        let float_time = measure(|| float = Some(aht20::decode(black_box(&buffer)).unwrap()));
+        let float = float.unwrap();
+        let mut fixed = (0, 0);
+        let fixed_time = measure(|| fixed = convert_fixed(black_box(&buffer)));
+
+        println!("Measure overhead: {} cycles", overhead);
+        report("I2C trigger (3 bytes)", trigger, overhead);
+        report("I2C read (7 bytes)", transfer, overhead);
+        report("float decode + CRC", float_time, overhead);
+        report("fixed-point conversion", fixed_time, overhead);
+        println!(
+            "Temperature: {:.2} °C / {}.{:02} °C, Humidity: {:.2} % / {}.{:02} %",
+            float.temperature,
+            fixed.0 / 100,
+            fixed.0.unsigned_abs() % 100,
+            float.humidity,
+            fixed.1 / 100,
+            fixed.1 % 100
+        );
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
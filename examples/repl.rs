@@ -0,0 +1,273 @@
+//! Interactive shell over the USB-Serial-JTAG port with history and completion
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8 (the onboard LED, lit when the pin is low)
+//! - optional analog input (0..2.5V) => GPIO3
+//!
+//! Open the port with a terminal that passes keys through as typed, e.g.
+//! `cargo espflash monitor`, `picocom /dev/ttyACM0` or PuTTY, and type
+//! `help`. Supported editing keys:
+//! - Backspace deletes the last character, Ctrl-C discards the line
+//! - Up / Down walk through the last `HISTORY_LEN` commands
+//! - Tab completes a command name; with several candidates it lists them
+//!
+//! Arrow keys arrive as ANSI escape sequences, ESC '[' followed by 'A' (up)
+//! or 'B' (down), so input goes through a small state machine that collects
+//! those instead of treating them as text. Left/Right are recognized and
+//! ignored, editing only happens at the end of the line.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    gpio::{Level, Output, OutputConfig},
+    main,
+    time::Instant,
+    usb_serial_jtag::UsbSerialJtag,
+};
+use heapless::String;
+
+const LINE_LEN: usize = 64;
+const HISTORY_LEN: usize = 8;
+const PROMPT: &str = "> ";
+
+const COMMANDS: [(&str, &str); 6] = [
+    ("help", "list commands"),
+    ("led", "led on|off|toggle"),
+    ("adc", "raw reading of GPIO3"),
+    ("uptime", "time since boot"),
+    ("history", "list recent commands"),
+    ("clear", "clear the screen"),
+];
+
+type Line = String<LINE_LEN>;
+
+/// The last `HISTORY_LEN` lines, oldest first
+struct History {
+    entries: [Line; HISTORY_LEN],
+    len: usize,
+    /// Next slot to write, the ring wraps around
+    head: usize,
+}
+
+impl History {
+    fn new() -> Self {
+        History {
+            entries: core::array::from_fn(|_| Line::new()),
+            len: 0,
+            head: 0,
+        }
+    }
+
+    fn push(&mut self, line: &str) {
+        // Repeating the previous command does not fill the history
+        if self.len > 0 && self.get(self.len - 1) == Some(line) {
+            return;
+        }
+        self.entries[self.head].clear();
+        self.entries[self.head].push_str(line).ok();
+        self.head = (self.head + 1) % HISTORY_LEN;
+        self.len = (self.len + 1).min(HISTORY_LEN);
+    }
+
+    /// Entry `i`, 0 being the oldest
+    fn get(&self, i: usize) -> Option<&str> {
+        if i >= self.len {
+            return None;
+        }
+        let index = (self.head + HISTORY_LEN - self.len + i) % HISTORY_LEN;
+        Some(self.entries[index].as_str())
+    }
+}
+
+enum Escape {
+    None,
+    /// Got ESC
+    Esc,
+    /// Got ESC '['
+    Csi,
+}
+
+struct Editor {
+    line: Line,
+    escape: Escape,
+    /// Position while browsing the history, `history.len` means the new line
+    browsing: usize,
+}
+
+/// Redraws the prompt and the line in place
+fn redraw(out: &mut impl Write, line: &str) {
+    write!(out, "\r\x1b[K{}{}", PROMPT, line).ok();
+}
+
+impl Editor {
+    fn new() -> Self {
+        Editor {
+            line: Line::new(),
+            escape: Escape::None,
+            browsing: 0,
+        }
+    }
+
+    /// Feeds one received byte, returns a complete line on Enter
+    fn feed(&mut self, byte: u8, history: &History, out: &mut impl Write) -> Option<Line> {
+        match self.escape {
+            Escape::Esc => {
+                self.escape = if byte == b'[' {
+                    Escape::Csi
+                } else {
+                    Escape::None
+                };
+                return None;
+            }
+            Escape::Csi => {
+                self.escape = Escape::None;
+                match byte {
+                    b'A' if self.browsing > 0 => self.browsing -= 1,
+                    b'B' if self.browsing < history.len => self.browsing += 1,
+                    _ => return None,
+                }
+                self.line.clear();
+                if let Some(entry) = history.get(self.browsing) {
+                    self.line.push_str(entry).ok();
+                }
+                redraw(out, &self.line);
+                return None;
+            }
+            Escape::None => {}
+        }
+
+        match byte {
+            0x1B => self.escape = Escape::Esc,
+            b'\r' | b'\n' => {
+                write!(out, "\r\n").ok();
+                let line = core::mem::take(&mut self.line);
+                return Some(line);
+            }
+            // Backspace (Ctrl-H) or Delete, terminals send either
+            0x08 | 0x7F => {
+                if self.line.pop().is_some() {
+                    write!(out, "\x08 \x08").ok();
+                }
+            }
+            // Ctrl-C
+            0x03 => {
+                self.line.clear();
+                write!(out, "^C\r\n{}", PROMPT).ok();
+            }
+            b'\t' => self.complete(out),
+            0x20..=0x7E => {
+                if self.line.push(byte as char).is_ok() {
+                    write!(out, "{}", byte as char).ok();
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Completes the command name being typed, or lists the candidates
+    fn complete(&mut self, out: &mut impl Write) {
+        // Only the first word is a command name
+        if self.line.contains(' ') {
+            return;
+        }
+        let prefix = self.line.as_str();
+        let mut matches = COMMANDS.iter().filter(|(name, _)| name.starts_with(prefix));
+        let first = matches.next();
+        match (first, matches.next()) {
+            (Some((name, _)), None) => {
+                self.line.clear();
+                self.line.push_str(name).ok();
+                self.line.push(' ').ok();
+                redraw(out, &self.line);
+            }
+            (Some(_), Some(_)) => {
+                write!(out, "\r\n").ok();
+                for (name, _) in COMMANDS.iter().filter(|(name, _)| name.starts_with(prefix)) {
+                    write!(out, "{}  ", name).ok();
+                }
+                write!(out, "\r\n").ok();
+                redraw(out, &self.line);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut led = Output::new(peripherals.GPIO8, Level::High, OutputConfig::default());
+
+    let mut adc1_config = AdcConfig::new();
+    let mut adc_pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let mut usb = UsbSerialJtag::new(peripherals.USB_DEVICE);
+
+    let mut history = History::new();
+    let mut editor = Editor::new();
+
+    write!(usb, "\r\nType 'help' for a list of commands\r\n{}", PROMPT).ok();
+
+    loop {
+        let byte = nb::block!(usb.read_byte()).unwrap();
+        let Some(line) = editor.feed(byte, &history, &mut usb) else {
+            continue;
+        };
+
+        let line = line.trim();
+        if !line.is_empty() {
+            history.push(line);
+        }
+        // Up arrow starts again from the newest entry
+        editor.browsing = history.len;
+
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (None, _) => {}
+            (Some("help"), _) => {
+                for (name, help) in COMMANDS {
+                    write!(usb, "{:<8} {}\r\n", name, help).ok();
+                }
+            }
+            (Some("led"), Some("on")) => led.set_low(),
+            (Some("led"), Some("off")) => led.set_high(),
+            (Some("led"), Some("toggle")) => led.toggle(),
+            (Some("led"), _) => {
+                write!(usb, "usage: led on|off|toggle\r\n").ok();
+            }
+            (Some("adc"), _) => {
+                let raw: u16 = nb::block!(adc1.read_oneshot(&mut adc_pin)).unwrap();
+                write!(usb, "GPIO3: {}\r\n", raw).ok();
+            }
+            (Some("uptime"), _) => {
+                let ms = Instant::now().duration_since_epoch().as_millis();
+                write!(usb, "{}.{:03} s\r\n", ms / 1000, ms % 1000).ok();
+            }
+            (Some("history"), _) => {
+                for i in 0..history.len {
+                    if let Some(entry) = history.get(i) {
+                        write!(usb, "{:>2}  {}\r\n", i + 1, entry).ok();
+                    }
+                }
+            }
+            (Some("clear"), _) => {
+                write!(usb, "\x1b[2J\x1b[H").ok();
+            }
+            (Some(command), _) => {
+                write!(usb, "unknown command '{}', try 'help'\r\n", command).ok();
+            }
+        }
+
+        write!(usb, "{}", PROMPT).ok();
+    }
+}
@@ -0,0 +1,127 @@
+//! Drives a plain 4-pin RGB LED with three LEDC PWM channels
+//!
+//! The following wiring is assumed (each through a ~220 Ohm resistor):
+//! - red => GPIO3
+//! - green => GPIO4
+//! - blue => GPIO5
+//! - common pin => GND for common-cathode, 3V3 for common-anode
+//!
+//! With a common-cathode LED a color lights up when its pin is high, with a
+//! common-anode LED when it is low. Set `COMMON_ANODE` to match the part,
+//! `RgbLed` inverts the duty cycles accordingly.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{color::Color, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    ledc::{
+        channel::{self, Channel, ChannelIFace},
+        timer::{self, TimerIFace},
+        LSGlobalClkSource, Ledc, LowSpeed,
+    },
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const COMMON_ANODE: bool = false;
+
+struct RgbLed<'a> {
+    red: Channel<'a, LowSpeed>,
+    green: Channel<'a, LowSpeed>,
+    blue: Channel<'a, LowSpeed>,
+    common_anode: bool,
+}
+
+impl<'a> RgbLed<'a> {
+    fn new(
+        red: Channel<'a, LowSpeed>,
+        green: Channel<'a, LowSpeed>,
+        blue: Channel<'a, LowSpeed>,
+        common_anode: bool,
+    ) -> Self {
+        let mut led = RgbLed {
+            red,
+            green,
+            blue,
+            common_anode,
+        };
+        led.set_color(Color::OFF);
+        led
+    }
+
+    /// Maps a 0-255 component to a duty cycle in percent
+    fn duty(&self, value: u8) -> u8 {
+        let pct = (value as u16 * 100 / 255) as u8;
+        if self.common_anode {
+            100 - pct
+        } else {
+            pct
+        }
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.red.set_duty(self.duty(color.r)).unwrap();
+        self.green.set_duty(self.duty(color.g)).unwrap();
+        self.blue.set_duty(self.duty(color.b)).unwrap();
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: Rate::from_khz(1),
+        })
+        .unwrap();
+
+    // All three channels share one timer, only their duty cycles differ
+    let channel_config = channel::config::Config {
+        timer: &lstimer0,
+        duty_pct: 0,
+        pin_config: channel::config::PinConfig::PushPull,
+    };
+    let mut red = ledc.channel(channel::Number::Channel0, peripherals.GPIO3);
+    red.configure(channel_config).unwrap();
+    let mut green = ledc.channel(channel::Number::Channel1, peripherals.GPIO4);
+    green.configure(channel_config).unwrap();
+    let mut blue = ledc.channel(channel::Number::Channel2, peripherals.GPIO5);
+    blue.configure(channel_config).unwrap();
+
+    let mut led = RgbLed::new(red, green, blue, COMMON_ANODE);
+    let delay = Delay::new();
+
+    let primaries = [
+        ("red", Color::rgb(255, 0, 0)),
+        ("green", Color::rgb(0, 255, 0)),
+        ("blue", Color::rgb(0, 0, 255)),
+        ("white", Color::rgb(255, 255, 255)),
+    ];
+
+    loop {
+        for (name, color) in primaries {
+            println!("{}: {:?}", name, color);
+            led.set_color(color);
+            delay.delay_millis(1000);
+        }
+
+        println!("Color wheel");
+        for _ in 0..3 {
+            for pos in 0..=255u8 {
+                led.set_color(Color::wheel(pos));
+                delay.delay_millis(10);
+            }
+        }
+    }
+}
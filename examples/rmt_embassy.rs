@@ -13,6 +13,7 @@
 
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
+use esp32_c3_super_mini_rust::print_banner;
 use esp_backtrace as _;
 use esp_hal::{
     gpio::Level,
@@ -26,6 +27,7 @@ use esp_println::println;
 async fn main(_spawner: Spawner) {
     println!("Init!");
     let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
 
     let timg0 = TimerGroup::new(peripherals.TIMG0);
     esp_hal_embassy::init(timg0.timer0);
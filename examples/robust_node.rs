@@ -0,0 +1,113 @@
+//! Battery sensor node that samples, publishes and deep-sleeps, with the RTC
+//! watchdog as a safety net against hangs
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! Every wake-up runs one cycle: arm the watchdog, read the AHT20, publish
+//! the reading, disarm the watchdog and go back to deep sleep. If any step
+//! hangs (a stuck I2C bus, a Wi-Fi connect that never finishes) the watchdog
+//! is not disarmed in time and resets the chip, which starts a fresh cycle
+//! instead of draining the battery while stuck awake.
+//!
+//! The reset reason printed on boot tells the cases apart:
+//! - power-on: first start
+//! - deep sleep: normal wake-up after a cycle
+//! - RTC watchdog: the previous cycle hung and was recovered
+//!
+//! Set `SIMULATE_HANG` to watch a recovery happen.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    rtc_cntl::{reset_reason, sleep::TimerWakeupSource, Rtc, RwdtStage, SocResetReason},
+    system::Cpu,
+    time::{Duration, Rate},
+};
+use esp_println::println;
+
+// Longest a whole cycle may take before it is considered hung. Leave room
+// for the slowest step, a Wi-Fi connect can easily take a few seconds.
+const WATCHDOG_TIMEOUT_MS: u64 = 10_000;
+const SLEEP_SECS: u64 = 60;
+
+const SIMULATE_HANG: bool = false;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut rtc = Rtc::new(peripherals.LPWR);
+    // Arm the watchdog first thing, everything after this is covered
+    rtc.rwdt.set_timeout(
+        RwdtStage::Stage0,
+        Duration::from_millis(WATCHDOG_TIMEOUT_MS),
+    );
+    rtc.rwdt.enable();
+
+    match reset_reason(Cpu::ProCpu) {
+        Some(SocResetReason::ChipPowerOn) => println!("Boot: power-on"),
+        Some(SocResetReason::CoreDeepSleep) => println!("Boot: woke up from deep sleep"),
+        Some(
+            SocResetReason::CoreRtcWdt | SocResetReason::SysRtcWdt | SocResetReason::Cpu0RtcWdt,
+        ) => println!("Boot: recovered from a hang by the RTC watchdog"),
+        other => println!("Boot: other reset reason {:?}", other),
+    }
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    let reading = aht20.start(&mut i2c).and_then(|_| aht20.read(&mut i2c));
+
+    if SIMULATE_HANG {
+        println!(
+            "Simulating a hang, the watchdog resets in {} ms",
+            WATCHDOG_TIMEOUT_MS
+        );
+        loop {
+            delay.delay_millis(1000);
+        }
+    }
+
+    // Publish step. A real node would connect to Wi-Fi and send the reading
+    // here, the watchdog covers that just the same.
+    match reading {
+        Ok(Measurement {
+            temperature,
+            humidity,
+        }) => println!(
+            "Temperature: {:.2} °C, Humidity: {:.2} %",
+            temperature, humidity
+        ),
+        Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+    }
+
+    // Cycle finished, the watchdog must not fire while asleep
+    rtc.rwdt.disable();
+
+    println!("Sleeping for {} s", SLEEP_SECS);
+    let timer = TimerWakeupSource::new(core::time::Duration::from_secs(SLEEP_SECS));
+    rtc.sleep_deep(&[&timer]);
+}
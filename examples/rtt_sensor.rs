@@ -0,0 +1,102 @@
+//! Streams AHT20 readings over RTT instead of the serial port
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! RTT (Real-Time Transfer) puts ring buffers in RAM that a debug probe reads
+//! through the debug interface while the chip keeps running. The C3's
+//! built-in USB-JTAG is such a probe, so the same USB cable works, no serial
+//! monitor needed.
+//!
+//! Two up channels are set up:
+//! - 0 "Terminal": human readable log, used by `rprintln!`
+//! - 1 "Data": one `temperature,humidity` line per reading for tools
+//!
+//! Flash and watch with probe-rs:
+//! `cargo build --release --example rtt_sensor`
+//! `probe-rs run --chip esp32c3 target/riscv32imc-unknown-none-elf/release/examples/rtt_sensor`
+//!
+//! `probe-rs run` shows channel 0. To pick up the data channel from another
+//! tool, attach with `probe-rs attach` and select the "Data" channel, or use
+//! any RTT viewer that lists channels by name.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::Rate,
+};
+use heapless::String;
+use rtt_target::{rprintln, rtt_init, set_print_channel};
+
+#[main]
+fn main() -> ! {
+    let channels = rtt_init! {
+        up: {
+            0: {
+                size: 1024,
+                name: "Terminal"
+            }
+            1: {
+                size: 1024,
+                name: "Data"
+            }
+        }
+    };
+    set_print_channel(channels.up.0);
+    let mut data = channels.up.1;
+
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    rprintln!("AHT20 sensor initialized, streaming over RTT");
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => {
+                rprintln!(
+                    "Temperature: {:.2} °C, Humidity: {:.2} %",
+                    temperature,
+                    humidity
+                );
+
+                let mut line: String<32> = String::new();
+                writeln!(line, "{:.2},{:.2}", temperature, humidity).ok();
+                data.write(line.as_bytes());
+            }
+            Err(e) => rprintln!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
@@ -0,0 +1,89 @@
+//! Logs from several embassy tasks without their lines getting mixed up
+//!
+//! No wiring needed.
+//!
+//! `println!` is only safe as long as one line is written by one call that
+//! nobody interrupts. A line built from several `print!` calls with an
+//! `.await` in between, or a `println!` from a task on an interrupt
+//! executor preempting another one, ends up interleaved with other output.
+//!
+//! Here no task prints directly. Each one formats its whole line into a
+//! fixed-size `heapless::String` and sends it over a channel, and a single
+//! logger task is the only place that prints. Lines come out whole and in
+//! the order they were sent. When the channel is full a sender waits instead
+//! of dropping its line.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use embassy_executor::Spawner;
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, channel::Channel};
+use embassy_time::{Duration, Instant, Timer};
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println::println;
+use heapless::String;
+
+const LINE_LEN: usize = 96;
+const QUEUE_LEN: usize = 8;
+
+type Line = String<LINE_LEN>;
+
+static LOG: Channel<CriticalSectionRawMutex, Line, QUEUE_LEN> = Channel::new();
+
+/// Formats one line with a timestamp and queues it for the logger task.
+/// Lines longer than `LINE_LEN` are cut off.
+macro_rules! log {
+    ($($arg:tt)*) => {{
+        let mut line = Line::new();
+        write!(line, "[{:>8}] ", Instant::now().as_millis()).ok();
+        write!(line, $($arg)*).ok();
+        LOG.send(line).await;
+    }};
+}
+
+#[embassy_executor::task]
+async fn logger_task() {
+    loop {
+        let line = LOG.receive().await;
+        println!("{}", line);
+    }
+}
+
+#[embassy_executor::task(pool_size = 3)]
+async fn worker_task(name: &'static str, period: Duration) {
+    let mut count = 0u32;
+    loop {
+        log!("{:<8} step {} started", name, count);
+        // Something in the middle of the work yields to the other tasks
+        Timer::after(period / 2).await;
+        log!("{:<8} step {} done", name, count);
+
+        count += 1;
+        Timer::after(period / 2).await;
+    }
+}
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    spawner.spawn(logger_task()).unwrap();
+    spawner
+        .spawn(worker_task("sensor", Duration::from_millis(300)))
+        .unwrap();
+    spawner
+        .spawn(worker_task("network", Duration::from_millis(700)))
+        .unwrap();
+    spawner
+        .spawn(worker_task("display", Duration::from_millis(1100)))
+        .unwrap();
+
+    log!("all tasks started");
+}
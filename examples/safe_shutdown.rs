@@ -0,0 +1,54 @@
+//! Puts all outputs into a safe state before a crash halts the chip
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8
+//! - Relay module (active high) => GPIO3
+//!
+//! Needs the `safe-shutdown` feature, which makes esp-backtrace call
+//! `custom_pre_backtrace` first thing in the panic handler:
+//! `cargo espflash flash --release --example safe_shutdown --features safe-shutdown`
+//!
+//! Without it a panic leaves the relay in whatever state it was, which for a
+//! heater or a pump is the worst possible outcome. Both outputs register
+//! their safe level with `safe_shutdown::register`, any other example can do
+//! the same. The example energizes the relay and then panics on purpose, the
+//! relay should drop immediately.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{board, print_banner, safe_shutdown};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+};
+use esp_println::println;
+
+const RELAY: u8 = 3;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = OutputConfig::default();
+    let mut led = Output::new(peripherals.GPIO8, Level::High, config);
+    let mut relay = Output::new(peripherals.GPIO3, Level::Low, config);
+
+    // The onboard LED is active low
+    safe_shutdown::register(board::LED, Level::High);
+    safe_shutdown::register(RELAY, Level::Low);
+
+    let delay = Delay::new();
+
+    for i in (1..=5).rev() {
+        relay.set_high();
+        led.toggle();
+        println!("Relay on, simulated fault in {} s", i);
+        delay.delay_millis(1000);
+    }
+
+    panic!("Simulated fault with the relay energized");
+}
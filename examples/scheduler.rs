@@ -0,0 +1,97 @@
+//! Runs several periodic jobs at different rates from one blocking loop
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8
+//!
+//! Three jobs share the loop: the LED blinks every 500ms, a (simulated)
+//! sensor is read every 2s and the collected readings are "published"
+//! every 30s. No nested delays, each job only says how often it wants to run.
+
+#![no_std]
+#![no_main]
+
+use core::cell::Cell;
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+    time::{Duration, Instant},
+};
+use esp_println::println;
+
+struct Job<'a> {
+    interval: Duration,
+    last_run: Instant,
+    callback: &'a mut dyn FnMut(),
+}
+
+impl<'a> Job<'a> {
+    fn new(interval: Duration, callback: &'a mut dyn FnMut()) -> Self {
+        Job {
+            interval,
+            last_run: Instant::now(),
+            callback,
+        }
+    }
+}
+
+/// Fixed set of periodic jobs, no allocation needed.
+struct Scheduler<'a, const N: usize> {
+    jobs: [Job<'a>; N],
+}
+
+impl<'a, const N: usize> Scheduler<'a, N> {
+    fn new(jobs: [Job<'a>; N]) -> Self {
+        Scheduler { jobs }
+    }
+
+    /// Runs every job whose interval has elapsed. Call it as often as
+    /// possible, the loop delay only limits how late a job may fire.
+    fn tick(&mut self) {
+        let now = Instant::now();
+        for job in self.jobs.iter_mut() {
+            if now - job.last_run >= job.interval {
+                job.last_run = now;
+                (job.callback)();
+            }
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = OutputConfig::default();
+    let mut led = Output::new(peripherals.GPIO8, Level::High, config);
+
+    let delay = Delay::new();
+
+    // Shared between the sensor and publish jobs
+    let readings = Cell::new(0u32);
+
+    let mut blink = || led.toggle();
+    let mut read_sensor = || {
+        readings.set(readings.get() + 1);
+        println!("Sensor read #{}", readings.get());
+    };
+    let mut publish = || {
+        println!("Publishing {} readings", readings.get());
+        readings.set(0);
+    };
+
+    let mut scheduler = Scheduler::new([
+        Job::new(Duration::from_millis(500), &mut blink),
+        Job::new(Duration::from_millis(2000), &mut read_sensor),
+        Job::new(Duration::from_millis(30_000), &mut publish),
+    ]);
+
+    loop {
+        scheduler.tick();
+        delay.delay_millis(10);
+    }
+}
@@ -0,0 +1,149 @@
+//! Logs the AHT20 temperature from a cold start to measure warm-up drift
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! Sensors read high after power-on: the chip heats itself with every
+//! measurement, and on a small board the ESP32-C3 and the regulator sit
+//! millimeters away and warm the air around the sensor. This example
+//! measures that drift instead of compensating for it (the warmup example
+//! does that). Power the board up after it sat unpowered for a while, then
+//! leave it alone for `DURATION_S`.
+//!
+//! Every `SAMPLE_INTERVAL_S` the temperature is printed with its offset
+//! from the first reading and a bar graph. At the end the last minute is
+//! taken as the settled value, and the time after which every reading
+//! stayed within `SETTLE_BAND` of it is reported. Use that as the
+//! stabilization delay before trusting readings. Running it with different
+//! sample intervals shows how much of the drift comes from measuring often.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+};
+use esp_println::{print, println};
+
+const SAMPLE_INTERVAL_S: u64 = 5;
+const DURATION_S: u64 = 600;
+const NUM_SAMPLES: usize = (DURATION_S / SAMPLE_INTERVAL_S) as usize;
+// Samples averaged for the settled value
+const SETTLED_SAMPLES: usize = (60 / SAMPLE_INTERVAL_S) as usize;
+// Within this many °C of the settled value counts as warmed up
+const SETTLE_BAND: f32 = 0.1;
+// One '#' per this many °C of drift
+const BAR_STEP: f32 = 0.05;
+
+/// Prints the offset as a bar of '#' (warmer) or '-' (cooler)
+fn print_bar(offset: f32) {
+    let symbol = if offset < 0.0 { '-' } else { '#' };
+    let len = ((if offset < 0.0 { -offset } else { offset }) / BAR_STEP) as usize;
+    for _ in 0..len.min(60) {
+        print!("{}", symbol);
+    }
+    println!();
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let delay = Delay::new();
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    println!(
+        "Logging for {} s, one sample every {} s",
+        DURATION_S, SAMPLE_INTERVAL_S
+    );
+
+    let start = Instant::now();
+    let mut next = start;
+    let mut samples = [0.0f32; NUM_SAMPLES];
+    let mut count = 0;
+
+    while count < NUM_SAMPLES {
+        while Instant::now() < next {
+            delay.delay_millis(10);
+        }
+        next += Duration::from_secs(SAMPLE_INTERVAL_S);
+
+        let temperature = match aht20.read(&mut i2c) {
+            Ok(Measurement { temperature, .. }) => temperature,
+            Err(e) => {
+                println!("Failed to read data from AHT20: {:?}", e);
+                continue;
+            }
+        };
+        samples[count] = temperature;
+        count += 1;
+
+        let offset = temperature - samples[0];
+        print!(
+            "{:>4} s {:>7.2} °C {:>+6.2} ",
+            (Instant::now() - start).as_secs(),
+            temperature,
+            offset
+        );
+        print_bar(offset);
+    }
+
+    let settled =
+        samples[NUM_SAMPLES - SETTLED_SAMPLES..].iter().sum::<f32>() / SETTLED_SAMPLES as f32;
+    let (min, max) = samples.iter().fold((f32::MAX, f32::MIN), |(min, max), &t| {
+        (min.min(t), max.max(t))
+    });
+    // Index of the last sample outside the band, everything after it stayed in
+    let settled_from = samples
+        .iter()
+        .rposition(|&t| (t - settled) > SETTLE_BAND || (settled - t) > SETTLE_BAND)
+        .map_or(0, |i| i + 1);
+
+    println!("--------");
+    println!(
+        "First: {:.2} °C, settled: {:.2} °C, drift: {:+.2} °C (range {:.2}..{:.2})",
+        samples[0],
+        settled,
+        settled - samples[0],
+        min,
+        max
+    );
+    if settled_from < NUM_SAMPLES - SETTLED_SAMPLES {
+        println!(
+            "Within ±{:.2} °C of the settled value after {} s",
+            SETTLE_BAND,
+            settled_from as u64 * SAMPLE_INTERVAL_S
+        );
+    } else {
+        println!("Still drifting at the end, run it for longer");
+    }
+
+    loop {
+        delay.delay_millis(1000);
+    }
+}
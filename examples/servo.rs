@@ -0,0 +1,193 @@
+//! Drives a hobby servo with per-servo calibration
+//!
+//! The following wiring is assumed:
+//! - servo signal (orange/yellow) => GPIO5
+//! - servo power (red) => 5V, ground (brown/black) => GND
+//!
+//! Servos take a 50 Hz pulse, nominally 1000 us for 0° to 2000 us for 180°.
+//! Cheap ones differ from unit to unit: the end stops sit at other pulse
+//! widths, "90°" is a few degrees off, the response is not linear and near
+//! the end stops they press against the mechanism and buzz. `Calibration`
+//! covers all of that:
+//! - `min_us` / `max_us`: pulse at 0° and 180°, trim until the horn just
+//!   reaches the end positions without buzzing
+//! - `center_offset_us`: added at 90°, blended out towards the ends, so
+//!   the center can be trimmed without moving the end points
+//! - `table`: optional pulse widths measured at 0, 45, 90, 135 and 180°,
+//!   replaces the three values above with a piecewise linear curve
+//! - `limits`: the angle range the servo is allowed to move in
+//! - `dead_zone_deg`: changes smaller than this are ignored, so a noisy
+//!   input does not keep the servo twitching
+//!
+//! The demo moves to 90° uncalibrated, then with a center trim, so the
+//! difference is visible. To trim your own servo, tune `CENTER_OFFSET_US`
+//! until the horn points straight at 90°.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    ledc::{
+        channel::{self, Channel, ChannelHW, ChannelIFace},
+        timer::{self, TimerIFace},
+        LSGlobalClkSource, Ledc, LowSpeed,
+    },
+    main,
+    time::Rate,
+};
+use esp_println::println;
+
+const PERIOD_US: u32 = 20_000;
+// 20 ms in 2^14 steps, about 1.2 us per step
+const DUTY_BITS: u32 = 14;
+
+const CENTER_OFFSET_US: i16 = 40;
+
+#[derive(Clone, Copy, Debug)]
+struct Calibration {
+    min_us: u16,
+    max_us: u16,
+    center_offset_us: i16,
+    table: Option<[u16; 5]>,
+    limits: (f32, f32),
+    dead_zone_deg: f32,
+}
+
+impl Calibration {
+    /// Nominal servo, no trim
+    const DEFAULT: Calibration = Calibration {
+        min_us: 1000,
+        max_us: 2000,
+        center_offset_us: 0,
+        table: None,
+        limits: (0.0, 180.0),
+        dead_zone_deg: 0.0,
+    };
+
+    /// Pulse width for `angle`, which must be within 0..=180
+    fn pulse_us(&self, angle: f32) -> f32 {
+        if let Some(table) = self.table {
+            let segment = ((angle / 45.0) as usize).min(3);
+            let t = (angle - segment as f32 * 45.0) / 45.0;
+            let (a, b) = (table[segment] as f32, table[segment + 1] as f32);
+            return a + (b - a) * t;
+        }
+
+        // max_us may be below min_us for a servo mounted the other way round
+        let linear = self.min_us as f32 + (self.max_us as f32 - self.min_us as f32) * angle / 180.0;
+        // Full offset at 90°, none at either end
+        let weight = 1.0
+            - if angle < 90.0 {
+                90.0 - angle
+            } else {
+                angle - 90.0
+            } / 90.0;
+        linear + self.center_offset_us as f32 * weight
+    }
+}
+
+struct Servo<'a> {
+    channel: Channel<'a, LowSpeed>,
+    calibration: Calibration,
+    angle: Option<f32>,
+}
+
+impl<'a> Servo<'a> {
+    fn new(channel: Channel<'a, LowSpeed>, calibration: Calibration) -> Self {
+        Servo {
+            channel,
+            calibration,
+            angle: None,
+        }
+    }
+
+    fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+        // Re-apply the current angle with the new calibration
+        if let Some(angle) = self.angle.take() {
+            self.set_angle(angle);
+        }
+    }
+
+    /// Moves to `angle` degrees, clamped to the calibrated limits
+    fn set_angle(&mut self, angle: f32) {
+        let (low, high) = self.calibration.limits;
+        let angle = angle.clamp(low, high);
+
+        if let Some(current) = self.angle {
+            let change = if angle > current {
+                angle - current
+            } else {
+                current - angle
+            };
+            if change < self.calibration.dead_zone_deg {
+                return;
+            }
+        }
+        self.angle = Some(angle);
+
+        let pulse_us = self.calibration.pulse_us(angle);
+        let duty = (pulse_us * (1 << DUTY_BITS) as f32 / PERIOD_US as f32) as u32;
+        self.channel.set_duty_hw(duty);
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty14Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: Rate::from_hz(1_000_000 / PERIOD_US),
+        })
+        .unwrap();
+    let mut channel0 = ledc.channel(channel::Number::Channel0, peripherals.GPIO5);
+    channel0
+        .configure(channel::config::Config {
+            timer: &lstimer0,
+            duty_pct: 0,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
+
+    let mut servo = Servo::new(channel0, Calibration::DEFAULT);
+    let delay = Delay::new();
+
+    println!("Uncalibrated: 90°");
+    servo.set_angle(90.0);
+    delay.delay_millis(2000);
+
+    let trimmed = Calibration {
+        center_offset_us: CENTER_OFFSET_US,
+        limits: (5.0, 175.0),
+        dead_zone_deg: 1.0,
+        ..Calibration::DEFAULT
+    };
+    println!("Center trimmed by {} us: 90°", CENTER_OFFSET_US);
+    servo.set_calibration(trimmed);
+    delay.delay_millis(2000);
+
+    loop {
+        for angle in [0.0, 45.0, 90.0, 135.0, 180.0, 90.0] {
+            servo.set_angle(angle);
+            if let Some(applied) = servo.angle {
+                println!(
+                    "Angle: {:.0}°, applied {:.0}° ({:.0} us)",
+                    angle,
+                    applied,
+                    servo.calibration.pulse_us(applied)
+                );
+            }
+            delay.delay_millis(1000);
+        }
+    }
+}
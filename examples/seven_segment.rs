@@ -0,0 +1,180 @@
+//! Shows the AHT20 temperature on a 4-digit 7-segment display driven
+//! through two 74HC595 shift registers over SPI
+//!
+//! The following wiring is assumed:
+//! - MOSI => GPIO6 => DS of the first 74HC595
+//! - SCK => GPIO4 => SH_CP of both
+//! - CS => GPIO7 => ST_CP (latch) of both
+//! - Q7' of the first 74HC595 => DS of the second
+//! - First 74HC595 Q0..Q7 => segments a..g and dp (through ~220 Ohm resistors)
+//! - Second 74HC595 Q0..Q3 => digit commons 1..4 (leftmost first)
+//! - AHT20 SDA => GPIO8, SCL => GPIO9
+//!
+//! The CS line doubles as the latch: it goes low for the transfer and its
+//! rising edge at the end copies the shifted bits to the outputs at once.
+//!
+//! Only one digit is lit at a time, the loop cycles through them fast enough
+//! (`DIGIT_TIME_US` each) that the eye sees all four. Within its slot each
+//! digit is only on for `BRIGHTNESS_PCT` of the time, which sets the
+//! brightness. The loop must never block for long or the display flickers,
+//! so the AHT20 measurement is started and collected later instead of
+//! waiting 80ms for it.
+//!
+//! Written for a common-cathode display: segments on when high, digits on
+//! when their common is low. Flip `SEGMENTS_ACTIVE_LOW` and
+//! `DIGITS_ACTIVE_LOW` for a common-anode one. Digit commons carry the
+//! current of up to 8 segments, drive them through transistors if the
+//! display is bright.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement, MEASUREMENT_TIME_MS},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    spi::{
+        master::{Config as SpiConfig, Spi},
+        Mode,
+    },
+    time::{Duration, Instant, Rate},
+};
+use esp_println::println;
+
+const NUM_DIGITS: usize = 4;
+const DIGIT_TIME_US: u32 = 2000;
+const BRIGHTNESS_PCT: u32 = 60;
+const SEGMENTS_ACTIVE_LOW: bool = false;
+const DIGITS_ACTIVE_LOW: bool = true;
+
+// Bit 0..7 = segment a..g, dp
+const DIGIT_SEGMENTS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F];
+const SEG_MINUS: u8 = 0x40;
+const SEG_DP: u8 = 0x80;
+
+const READ_INTERVAL_MS: u64 = 2000;
+
+/// Segment patterns for `value` with one decimal, right aligned, e.g.
+/// ` 23.4` or `-5.2`. Values that do not fit show as `----`.
+fn render(value: f32) -> [u8; NUM_DIGITS] {
+    let tenths = if value < 0.0 {
+        (value * 10.0 - 0.5) as i32
+    } else {
+        (value * 10.0 + 0.5) as i32
+    };
+    if !(-999..=9999).contains(&tenths) {
+        return [SEG_MINUS; NUM_DIGITS];
+    }
+
+    let mut segments = [0u8; NUM_DIGITS];
+    let mut rest = tenths.unsigned_abs();
+    for (i, segment) in segments.iter_mut().enumerate().rev() {
+        // Always show the tenths and the ones digit, blank leading zeros
+        if rest == 0 && i < NUM_DIGITS - 2 {
+            break;
+        }
+        *segment = DIGIT_SEGMENTS[(rest % 10) as usize];
+        rest /= 10;
+    }
+    segments[NUM_DIGITS - 2] |= SEG_DP;
+
+    if tenths < 0 {
+        // Minus sign right before the first digit in use
+        let first = segments.iter().position(|&s| s != 0).unwrap_or(0);
+        segments[first.saturating_sub(1)] = SEG_MINUS;
+    }
+    segments
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    // 74HC595 shifts on the rising clock edge, mode 0
+    let config = SpiConfig::default()
+        .with_frequency(Rate::from_mhz(4))
+        .with_mode(Mode::_0);
+    let mut spi = match Spi::new(peripherals.SPI2, config) {
+        Ok(spi) => spi,
+        Err(e) => {
+            panic!("Failed to initialize SPI: {:?}", e);
+        }
+    }
+    .with_sck(peripherals.GPIO4)
+    .with_mosi(peripherals.GPIO6)
+    .with_cs(peripherals.GPIO7);
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    // Writes segment and digit bytes, the digit byte goes first as it has to
+    // travel through the first register into the second one
+    let mut show = |segments: u8, digits: u8| {
+        let segments = if SEGMENTS_ACTIVE_LOW {
+            !segments
+        } else {
+            segments
+        };
+        let digits = if DIGITS_ACTIVE_LOW { !digits } else { digits };
+        spi.write(&[digits, segments]).unwrap();
+    };
+
+    let on_us = DIGIT_TIME_US * BRIGHTNESS_PCT / 100;
+    let mut display = [SEG_MINUS; NUM_DIGITS];
+    let mut next_read = Instant::now();
+    let mut result_at: Option<Instant> = None;
+
+    loop {
+        for (i, &segments) in display.iter().enumerate() {
+            show(segments, 1 << i);
+            delay.delay_micros(on_us);
+            show(0, 0);
+            delay.delay_micros(DIGIT_TIME_US - on_us);
+        }
+
+        let now = Instant::now();
+        match result_at {
+            None if now >= next_read => {
+                match aht20.trigger(&mut i2c) {
+                    Ok(_) => {
+                        result_at = Some(now + Duration::from_millis(MEASUREMENT_TIME_MS as u64))
+                    }
+                    Err(e) => println!("Failed to start AHT20 measurement: {:?}", e),
+                }
+                next_read = now + Duration::from_millis(READ_INTERVAL_MS);
+            }
+            Some(at) if now >= at => {
+                result_at = None;
+                match aht20.fetch(&mut i2c) {
+                    Ok(Measurement { temperature, .. }) => {
+                        println!("Temperature: {:.1} °C", temperature);
+                        display = render(temperature);
+                    }
+                    Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+                }
+            }
+            _ => {}
+        }
+    }
+}
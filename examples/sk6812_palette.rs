@@ -0,0 +1,172 @@
+//! Cycles a sk6812 RGBW LED strip through a color palette
+//!
+//! Connect a sk6812 RGBW LED strip to GPIO4.
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//!
+//! A palette is a fixed list of colors treated as a loop. Every pixel picks
+//! its color from a position along that loop, blending between the two
+//! nearest entries, and the positions move a little every frame. The result
+//! is a gradient that slides along the strip. Everything is integer math:
+//! positions are in 1/256ths of a palette step.
+//!
+//! Tune the look with:
+//! - `SPREAD` how far apart neighbouring pixels sit in the palette
+//! - `SPEED` how far the gradient moves per frame
+//! - `FRAME_TIME` the inter-frame time, the animation speed scales with it
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Ticker};
+use esp32_c3_super_mini_rust::{color::Color, config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+
+const FRAME_TIME: Duration = Duration::from_millis(20);
+const SPREAD: u32 = 64;
+const SPEED: u32 = 4;
+const FRAMES_PER_PALETTE: u32 = 500;
+
+struct Palette {
+    name: &'static str,
+    colors: &'static [Color],
+}
+
+impl Palette {
+    /// Length of the palette loop in 1/256ths of a step
+    fn len(&self) -> u32 {
+        self.colors.len() as u32 * 256
+    }
+
+    /// Color at `pos`, wrapping around from the last entry to the first
+    fn sample(&self, pos: u32) -> Color {
+        let pos = pos % self.len();
+        let idx = (pos / 256) as usize;
+        let next = (idx + 1) % self.colors.len();
+        self.colors[idx].lerp(self.colors[next], (pos % 256) as u8)
+    }
+}
+
+const FIRE: Palette = Palette {
+    name: "fire",
+    colors: &[
+        Color::new(40, 0, 0, 0),
+        Color::new(120, 10, 0, 0),
+        Color::new(160, 50, 0, 0),
+        Color::new(120, 80, 0, 10),
+    ],
+};
+
+const OCEAN: Palette = Palette {
+    name: "ocean",
+    colors: &[
+        Color::new(0, 0, 60, 0),
+        Color::new(0, 40, 120, 0),
+        Color::new(0, 100, 100, 0),
+        Color::new(0, 20, 80, 20),
+    ],
+};
+
+const RAINBOW: Palette = Palette {
+    name: "rainbow",
+    colors: &[
+        Color::new(80, 0, 0, 0),
+        Color::new(80, 80, 0, 0),
+        Color::new(0, 80, 0, 0),
+        Color::new(0, 80, 80, 0),
+        Color::new(0, 0, 80, 0),
+        Color::new(80, 0, 80, 0),
+    ],
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Direction {
+    Forward,
+    Reverse,
+}
+
+struct PaletteCycle<'a> {
+    palette: &'a Palette,
+    offset: u32,
+    spread: u32,
+    speed: u32,
+    direction: Direction,
+}
+
+impl<'a> PaletteCycle<'a> {
+    fn new(palette: &'a Palette, spread: u32, speed: u32, direction: Direction) -> Self {
+        PaletteCycle {
+            palette,
+            offset: 0,
+            spread,
+            speed,
+            direction,
+        }
+    }
+
+    /// Fills `frame` with the current gradient and moves it one step
+    fn next_frame(&mut self, frame: &mut [Color]) {
+        for (i, pixel) in frame.iter_mut().enumerate() {
+            *pixel = self.palette.sample(self.offset + i as u32 * self.spread);
+        }
+
+        let len = self.palette.len();
+        self.offset = match self.direction {
+            Direction::Forward => (self.offset + len - self.speed % len) % len,
+            Direction::Reverse => (self.offset + self.speed) % len,
+        };
+    }
+}
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    let palettes = [
+        (&FIRE, Direction::Forward),
+        (&OCEAN, Direction::Reverse),
+        (&RAINBOW, Direction::Forward),
+    ];
+    let mut frame = [Color::OFF; NUM_LEDS];
+    // A ticker keeps the frame rate steady no matter how long a frame takes to send
+    let mut ticker = Ticker::every(FRAME_TIME);
+
+    loop {
+        for &(palette, direction) in palettes.iter() {
+            println!("Palette: {}, direction: {:?}", palette.name, direction);
+
+            let mut cycle = PaletteCycle::new(palette, SPREAD, SPEED, direction);
+            for _ in 0..FRAMES_PER_PALETTE {
+                cycle.next_frame(&mut frame);
+                strip.flush(&frame.map(Color::to_rgbw)).await;
+                ticker.next().await;
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Tracks the daily minimum and maximum temperature across deep sleeps
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! Deep sleep powers down the CPU and the main RAM, every wake-up starts
+//! `main` from scratch. RTC RAM stays powered, so a small `Extremes` struct
+//! kept there accumulates min, max and count over all the wake-ups without
+//! touching flash (which wears out) or needing a connection. The C3 has no
+//! separate RTC slow memory, the 8 KB of RTC fast memory is what survives.
+//!
+//! After a power cycle RTC RAM holds random data. A magic value tells a
+//! valid struct from garbage, and a power-on reset starts from scratch even
+//! if the garbage happens to match. Once `SAMPLES_PER_DAY` wake-ups are
+//! collected the day's extremes are printed and a new day starts.
+
+#![no_std]
+#![no_main]
+
+use core::ptr::addr_of_mut;
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main, ram,
+    rtc_cntl::{reset_reason, sleep::TimerWakeupSource, Rtc, SocResetReason},
+    system::Cpu,
+    time::Rate,
+};
+use esp_println::println;
+
+const SLEEP_SECS: u64 = 300;
+const SAMPLES_PER_DAY: u32 = (24 * 3600 / SLEEP_SECS) as u32;
+const EXTREMES_MAGIC: u32 = 0xE87E_3E5A;
+
+#[derive(Clone, Copy)]
+struct Extremes {
+    magic: u32,
+    min: f32,
+    max: f32,
+    count: u32,
+    /// Wake-ups whose reading failed, so gaps show up
+    failures: u32,
+}
+
+impl Extremes {
+    const EMPTY: Extremes = Extremes {
+        magic: EXTREMES_MAGIC,
+        min: f32::MAX,
+        max: f32::MIN,
+        count: 0,
+        failures: 0,
+    };
+
+    fn add(&mut self, temperature: f32) {
+        self.min = self.min.min(temperature);
+        self.max = self.max.max(temperature);
+        self.count += 1;
+    }
+}
+
+#[ram(rtc_fast, persistent)]
+static mut EXTREMES: Extremes = Extremes::EMPTY;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut rtc = Rtc::new(peripherals.LPWR);
+
+    // Only this function touches EXTREMES, once per boot
+    let extremes = unsafe { &mut *addr_of_mut!(EXTREMES) };
+    let woke_from_sleep = matches!(
+        reset_reason(Cpu::ProCpu),
+        Some(SocResetReason::CoreDeepSleep)
+    );
+    if !woke_from_sleep || extremes.magic != EXTREMES_MAGIC {
+        println!("Cold boot, starting a new day");
+        *extremes = Extremes::EMPTY;
+    }
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    let reading = aht20.start(&mut i2c).and_then(|_| aht20.read(&mut i2c));
+
+    match reading {
+        Ok(Measurement { temperature, .. }) => {
+            extremes.add(temperature);
+            println!(
+                "Temperature: {:.2} °C, today: min {:.2} °C, max {:.2} °C ({} samples)",
+                temperature, extremes.min, extremes.max, extremes.count
+            );
+        }
+        Err(e) => {
+            extremes.failures += 1;
+            println!("Failed to read data from AHT20: {:?}", e);
+        }
+    }
+
+    if extremes.count + extremes.failures >= SAMPLES_PER_DAY {
+        println!(
+            "Day done: min {:.2} °C, max {:.2} °C from {} samples, {} failed",
+            extremes.min, extremes.max, extremes.count, extremes.failures
+        );
+        *extremes = Extremes::EMPTY;
+    }
+
+    println!("Sleeping for {} s", SLEEP_SECS);
+    let timer = TimerWakeupSource::new(core::time::Duration::from_secs(SLEEP_SECS));
+    rtc.sleep_deep(&[&timer]);
+}
@@ -0,0 +1,160 @@
+//! Reads a capacitive soil moisture probe with a calibration kept in flash
+//!
+//! The following wiring is assumed:
+//! - probe AOUT => GPIO3
+//! - probe VCC => 3V3, GND => GND
+//! - Button => GPIO0 -> GND
+//!
+//! Capacitive probes (the common "v1.2/v2.0" boards) output a voltage that
+//! drops as the soil gets wetter: more water, more capacitance, lower
+//! reading. The two ends differ from probe to probe and with the supply,
+//! so they are calibrated and stored in flash:
+//! - short press with the probe dry in air stores the "dry" reading
+//! - long press (over a second) with the probe in a glass of water, up to
+//!   the line marked on the probe, stores the "wet" reading
+//!
+//! Moisture is then the position between the two, 0 % at dry and 100 % at
+//! wet, clamped to that range. Each reading is averaged over `OVERSAMPLING`
+//! ADC reads, the probe output is quite noisy.
+
+#![no_std]
+#![no_main]
+
+use embedded_storage::{ReadStorage, Storage};
+use esp32_c3_super_mini_rust::{adc, flash, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    gpio::{Input, InputConfig, Pull},
+    main,
+    time::{Duration, Instant},
+};
+use esp_println::println;
+use esp_storage::FlashStorage;
+
+const CALIBRATION_ADDR: u32 = flash::SOIL_CALIBRATION_ADDR;
+const CALIBRATION_MAGIC: u8 = 0x5E;
+const RECORD_SIZE: usize = 8;
+
+const OVERSAMPLING: u32 = 32;
+const LONG_PRESS: Duration = Duration::from_millis(1000);
+const REPORT_INTERVAL_MS: u64 = 2000;
+
+#[derive(Debug, Clone, Copy)]
+struct Calibration {
+    /// Raw reading in air
+    dry: u16,
+    /// Raw reading in water
+    wet: u16,
+}
+
+impl Default for Calibration {
+    /// Typical values for a v1.2 probe on 3V3
+    fn default() -> Self {
+        Calibration {
+            dry: 3000,
+            wet: 1300,
+        }
+    }
+}
+
+impl Calibration {
+    /// Layout (little-endian): magic, unused, dry (u16), wet (u16)
+    fn load(flash: &mut FlashStorage) -> Self {
+        let mut bytes = [0u8; RECORD_SIZE];
+        if let Err(e) = flash.read(CALIBRATION_ADDR, &mut bytes) {
+            println!("Failed to read calibration: {:?}, using defaults", e);
+            return Calibration::default();
+        }
+        if bytes[0] != CALIBRATION_MAGIC {
+            println!("No calibration stored, using defaults");
+            return Calibration::default();
+        }
+        Calibration {
+            dry: u16::from_le_bytes([bytes[2], bytes[3]]),
+            wet: u16::from_le_bytes([bytes[4], bytes[5]]),
+        }
+    }
+
+    fn save(&self, flash: &mut FlashStorage) {
+        let mut bytes = [0xFF; RECORD_SIZE];
+        bytes[0] = CALIBRATION_MAGIC;
+        bytes[2..4].copy_from_slice(&self.dry.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.wet.to_le_bytes());
+        if let Err(e) = flash.write(CALIBRATION_ADDR, &bytes) {
+            println!("Failed to write calibration: {:?}", e);
+        }
+    }
+
+    /// 0 % at the dry reading, 100 % at the wet one. Works whichever way
+    /// round the probe's output goes.
+    fn moisture_pct(&self, raw: u16) -> f32 {
+        if self.dry == self.wet {
+            return 0.0;
+        }
+        let pct = (self.dry as f32 - raw as f32) / (self.dry as f32 - self.wet as f32) * 100.0;
+        pct.clamp(0.0, 100.0)
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let button = Input::new(peripherals.GPIO0, in_config);
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let mut read_raw = || {
+        adc::read_oversampled(
+            || nb::block!(adc1.read_oneshot(&mut pin)).unwrap(),
+            OVERSAMPLING,
+        )
+    };
+
+    let mut flash = FlashStorage::new();
+    let mut calibration = Calibration::load(&mut flash);
+    println!("Calibration: {:?}", calibration);
+
+    let delay = Delay::new();
+    let mut next_report = Instant::now();
+
+    loop {
+        if button.is_low() {
+            let pressed_at = Instant::now();
+            while button.is_low() {
+                delay.delay_millis(10);
+            }
+            // Read after the release, so the hand is off the board
+            let raw = read_raw();
+            if Instant::now() - pressed_at >= LONG_PRESS {
+                calibration.wet = raw;
+                println!("Stored wet reference: {}", raw);
+            } else {
+                calibration.dry = raw;
+                println!("Stored dry reference: {}", raw);
+            }
+            calibration.save(&mut flash);
+            delay.delay_millis(50);
+        }
+
+        if Instant::now() >= next_report {
+            next_report = Instant::now() + Duration::from_millis(REPORT_INTERVAL_MS);
+            let raw = read_raw();
+            // 12-bit ADC with 11dB attenuation reads roughly 0..2500mV
+            println!(
+                "Moisture: {:.0} % (raw {}, {} mV)",
+                calibration.moisture_pct(raw),
+                raw,
+                raw as u32 * 2500 / 4095
+            );
+        }
+
+        delay.delay_millis(10);
+    }
+}
@@ -0,0 +1,108 @@
+//! Turns a distance reading into sound, like a car's parking sensor
+//!
+//! The following wiring is assumed:
+//! - passive buzzer (or small speaker through a transistor) => GPIO5
+//! - Sharp GP2Y0A21YK0F (10-80cm IR distance sensor) Vo => GPIO3
+//! - Sensor VCC => 5V, GND => GND, with a 10uF capacitor across VCC/GND
+//!
+//! The closer the object, the higher the pitch and the shorter the pause
+//! between beeps, down to a continuous tone inside `CONTINUOUS_CM`. Beyond
+//! `SILENT_CM` it stays quiet, so an empty room does not beep forever.
+//! The mapping lives in `Mapping`, change the distances or the pitch range
+//! there.
+//!
+//! The tone is a square wave toggled by hand with microsecond delays, which
+//! allows any pitch without reconfiguring a PWM timer. A passive buzzer is
+//! needed; an active one only knows its own built-in tone.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{gp2y0a21::millivolts_to_cm, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcConfig, Attenuation},
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    main,
+};
+use esp_println::println;
+
+const BEEP_MS: u32 = 60;
+
+/// How distance turns into sound
+struct Mapping {
+    /// Quiet at or beyond this distance
+    silent_cm: f32,
+    /// Continuous tone at or below this distance
+    continuous_cm: f32,
+    /// Pitch at `silent_cm` and at `continuous_cm`
+    low_hz: u32,
+    high_hz: u32,
+    /// Pause between beeps at `silent_cm`, it shrinks towards zero when closer
+    max_pause_ms: u32,
+}
+
+const MAPPING: Mapping = Mapping {
+    silent_cm: 60.0,
+    continuous_cm: 12.0,
+    low_hz: 600,
+    high_hz: 2400,
+    max_pause_ms: 600,
+};
+
+impl Mapping {
+    /// (frequency, pause) for a distance, `None` inside the silence zone
+    fn tone(&self, cm: f32) -> Option<(u32, u32)> {
+        if cm >= self.silent_cm {
+            return None;
+        }
+        // 0.0 at the far end, 1.0 at the continuous zone
+        let closeness =
+            ((self.silent_cm - cm) / (self.silent_cm - self.continuous_cm)).clamp(0.0, 1.0);
+        let hz = self.low_hz + ((self.high_hz - self.low_hz) as f32 * closeness) as u32;
+        let pause = (self.max_pause_ms as f32 * (1.0 - closeness)) as u32;
+        Some((hz, pause))
+    }
+}
+
+/// Plays a square wave of `hz` for `ms`
+fn beep(buzzer: &mut Output, delay: &Delay, hz: u32, ms: u32) {
+    let half_period_us = 500_000 / hz.max(1);
+    let cycles = ms * 1000 / (2 * half_period_us);
+    for _ in 0..cycles {
+        buzzer.set_high();
+        delay.delay_micros(half_period_us);
+        buzzer.set_low();
+        delay.delay_micros(half_period_us);
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut buzzer = Output::new(peripherals.GPIO5, Level::Low, OutputConfig::default());
+
+    let mut adc1_config = AdcConfig::new();
+    let mut pin = adc1_config.enable_pin(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    let delay = Delay::new();
+
+    loop {
+        // 12-bit ADC with 11dB attenuation reads roughly 0..2500mV
+        let raw: u16 = nb::block!(adc1.read_oneshot(&mut pin)).unwrap();
+        let cm = millivolts_to_cm(raw as u32 * 2500 / 4095);
+
+        match MAPPING.tone(cm) {
+            Some((hz, pause_ms)) => {
+                println!("Distance: {:.0} cm, {} Hz, pause {} ms", cm, hz, pause_ms);
+                beep(&mut buzzer, &delay, hz, BEEP_MS);
+                delay.delay_millis(pause_ms);
+            }
+            None => delay.delay_millis(100),
+        }
+    }
+}
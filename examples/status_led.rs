@@ -0,0 +1,145 @@
+//! Status LED with semantic patterns and adjustable brightness
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8 (the onboard LED, lit when the pin is low)
+//!
+//! `StatusLed` drives the LED through LEDC PWM, so every pattern respects
+//! the configured brightness. `blink` and `pulse` block for their duration,
+//! `heartbeat` does not: call it every loop iteration and it keeps a
+//! double-blip going no matter what else the loop is busy with.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    ledc::{
+        channel::{self, Channel, ChannelIFace},
+        timer::{self, TimerIFace},
+        LSGlobalClkSource, Ledc, LowSpeed,
+    },
+    main,
+    time::{self, Rate},
+};
+use esp_println::println;
+
+const BRIGHTNESS_PCT: u8 = 20;
+
+struct StatusLed<'a> {
+    channel: Channel<'a, LowSpeed>,
+    brightness: u8,
+    active_low: bool,
+    delay: Delay,
+}
+
+impl<'a> StatusLed<'a> {
+    fn new(channel: Channel<'a, LowSpeed>, brightness: u8, active_low: bool) -> Self {
+        let mut led = StatusLed {
+            channel,
+            brightness: brightness.min(100),
+            active_low,
+            delay: Delay::new(),
+        };
+        led.off();
+        led
+    }
+
+    fn set_pct(&mut self, pct: u8) {
+        let duty = if self.active_low { 100 - pct } else { pct };
+        self.channel.set_duty(duty).unwrap();
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness.min(100);
+    }
+
+    fn on(&mut self) {
+        self.set_pct(self.brightness);
+    }
+
+    fn off(&mut self) {
+        self.set_pct(0);
+    }
+
+    /// Blinks `times` times, 150ms on and 150ms off
+    fn blink(&mut self, times: u8) {
+        for _ in 0..times {
+            self.on();
+            self.delay.delay_millis(150);
+            self.off();
+            self.delay.delay_millis(150);
+        }
+    }
+
+    /// Fades in and out once over one second
+    fn pulse(&mut self) {
+        for step in (0..=self.brightness).chain((0..self.brightness).rev()) {
+            self.set_pct(step);
+            self.delay
+                .delay_micros(500_000 / self.brightness.max(1) as u32);
+        }
+    }
+
+    /// Double blip every 1.2s, timed from the system clock so it can be
+    /// called as often as convenient without blocking
+    fn heartbeat(&mut self) {
+        let phase = time::Instant::now().duration_since_epoch().as_millis() % 1200;
+        if phase < 80 || (200..280).contains(&phase) {
+            self.on();
+        } else {
+            self.off();
+        }
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: Rate::from_khz(1),
+        })
+        .unwrap();
+    let mut channel0 = ledc.channel(channel::Number::Channel0, peripherals.GPIO8);
+    channel0
+        .configure(channel::config::Config {
+            timer: &lstimer0,
+            duty_pct: 100,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
+
+    let mut led = StatusLed::new(channel0, BRIGHTNESS_PCT, true);
+
+    println!("Booting: 3 blinks");
+    led.blink(3);
+    println!("Ready: one pulse at full brightness");
+    led.set_brightness(100);
+    led.pulse();
+    led.set_brightness(BRIGHTNESS_PCT);
+
+    let delay = Delay::new();
+    let mut last_report = 0;
+
+    loop {
+        led.heartbeat();
+
+        // Other work, the heartbeat keeps going in between
+        let now = time::Instant::now().duration_since_epoch().as_millis();
+        if now - last_report >= 5000 {
+            last_report = now;
+            println!("Uptime: {} s", now / 1000);
+        }
+
+        delay.delay_millis(10);
+    }
+}
@@ -0,0 +1,91 @@
+//! Measures how much the supply voltage drops when the LED strip lights up
+//!
+//! Connect a sk6812 RGBW LED strip to GPIO4.
+//!
+//! The following wiring is assumed:
+//! - led_strip_data => GPIO4
+//! - Strip supply => 100k => GPIO3 => 100k => GND (divider, halves the voltage)
+//!
+//! The supply is sampled with the strip dark, then the whole strip is
+//! switched to full white and the supply is sampled for a while to catch the
+//! lowest point. If the strip hangs on the board's 3.3V rail and the sag
+//! gets near the brownout level, the board will reset under load: power the
+//! strip from a separate 5V supply (common GND) instead.
+
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Timer};
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner, sk6812};
+use esp_backtrace as _;
+use esp_hal::{
+    analog::adc::{Adc, AdcCalCurve, AdcConfig, Attenuation},
+    peripherals::ADC1,
+    rmt::{Rmt, TxChannelConfig, TxChannelCreatorAsync},
+    time::Rate,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+
+const NUM_LEDS: usize = CONFIG.num_leds;
+const DIVIDER_RATIO: u32 = 2;
+// The C3 brownout detector trips around 2.7V, warn a bit above that
+const BROWNOUT_WARN_MV: u32 = 2900;
+const SAMPLE_WINDOW: Duration = Duration::from_millis(200);
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    esp_hal_embassy::init(timg0.timer0);
+
+    let mut adc1_config = AdcConfig::new();
+    let mut supply_pin = adc1_config
+        .enable_pin_with_cal::<_, AdcCalCurve<ADC1>>(peripherals.GPIO3, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+
+    // Calibrated reading in mV at the pin, scaled back up by the divider
+    let mut read_supply_mv =
+        || nb::block!(adc1.read_oneshot(&mut supply_pin)).unwrap() as u32 * DIVIDER_RATIO;
+
+    let freq = Rate::from_mhz(80);
+
+    let rmt = Rmt::new(peripherals.RMT, freq).unwrap().into_async();
+
+    let channel = rmt
+        .channel0
+        .configure(
+            peripherals.GPIO4,
+            TxChannelConfig::default().with_clk_divider(1),
+        )
+        .unwrap();
+    let mut strip = sk6812::Strip::new(channel);
+
+    loop {
+        strip.flush(&[(0, 0, 0, 0); NUM_LEDS]).await;
+        Timer::after(Duration::from_millis(500)).await;
+        let idle_mv = read_supply_mv();
+
+        strip.flush(&[(255, 255, 255, 255); NUM_LEDS]).await;
+        let start = Instant::now();
+        let mut min_mv = u32::MAX;
+        while Instant::now() - start < SAMPLE_WINDOW {
+            min_mv = min_mv.min(read_supply_mv());
+        }
+        strip.flush(&[(0, 0, 0, 0); NUM_LEDS]).await;
+
+        println!(
+            "Supply idle: {} mV, under load: {} mV, sag: {} mV",
+            idle_mv,
+            min_mv,
+            idle_mv.saturating_sub(min_mv)
+        );
+        if min_mv < BROWNOUT_WARN_MV {
+            println!("Warning: supply close to brownout, use an external supply for the strip");
+        }
+
+        Timer::after(Duration::from_secs(3)).await;
+    }
+}
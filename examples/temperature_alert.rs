@@ -0,0 +1,185 @@
+//! Shows the temperature status on the LED without blocking the sensor loop
+//!
+//! The following wiring is assumed:
+//! - LED => GPIO8 (the onboard LED, lit when the pin is low)
+//! - SDA => GPIO9
+//! - SCL => GPIO10
+//!
+//! The onboard LED and the usual SDA pin are both GPIO8, so the sensor moves
+//! to GPIO9/GPIO10 here.
+//!
+//! `Blinker` is a small state machine: `tick` is called every loop
+//! iteration, looks at the time and sets the LED accordingly, and returns
+//! right away. The loop can do whatever else it needs in between, as long
+//! as it comes back every few milliseconds. The AHT20 read is split into
+//! "start" and "collect 80 ms later" for the same reason.
+//!
+//! Patterns by temperature:
+//! - below `WARNING_C`: slow blink, all normal
+//! - from `WARNING_C`: fast blink
+//! - from `ALERT_C`: solid on
+//! - sensor errors: `NPulses(3)`, three short flashes and a pause
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement, MEASUREMENT_TIME_MS},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Level, Output, OutputConfig},
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+};
+use esp_println::println;
+
+const WARNING_C: f32 = 28.0;
+const ALERT_C: f32 = 35.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BlinkPattern {
+    Off,
+    /// 1 Hz, short flash
+    SlowBlink,
+    /// 5 Hz
+    FastBlink,
+    Solid,
+    /// `n` short flashes, then a pause, repeated
+    NPulses(u8),
+}
+
+impl BlinkPattern {
+    /// Whether the LED is lit `ms` after the pattern started
+    fn level_at(self, ms: u64) -> bool {
+        match self {
+            BlinkPattern::Off => false,
+            BlinkPattern::Solid => true,
+            BlinkPattern::SlowBlink => ms % 1000 < 100,
+            BlinkPattern::FastBlink => ms % 200 < 100,
+            BlinkPattern::NPulses(n) => {
+                // 150 ms on + 150 ms off per pulse, then a 1 s pause
+                let cycle = n as u64 * 300 + 1000;
+                let t = ms % cycle;
+                t < n as u64 * 300 && t % 300 < 150
+            }
+        }
+    }
+}
+
+/// Non-blocking LED pattern player
+struct Blinker<'a> {
+    led: Output<'a>,
+    pattern: BlinkPattern,
+    started: Instant,
+}
+
+impl<'a> Blinker<'a> {
+    fn new(led: Output<'a>) -> Self {
+        Blinker {
+            led,
+            pattern: BlinkPattern::Off,
+            started: Instant::now(),
+        }
+    }
+
+    /// Switches pattern, restarting its cycle only when it actually changed
+    fn set(&mut self, pattern: BlinkPattern) {
+        if pattern != self.pattern {
+            self.pattern = pattern;
+            self.started = Instant::now();
+        }
+    }
+
+    /// Call every loop iteration
+    fn tick(&mut self) {
+        let ms = (Instant::now() - self.started).as_millis();
+        // Onboard LED is lit when low
+        if self.pattern.level_at(ms) {
+            self.led.set_low();
+        } else {
+            self.led.set_high();
+        }
+    }
+}
+
+fn pattern_for(temperature: f32) -> BlinkPattern {
+    if temperature >= ALERT_C {
+        BlinkPattern::Solid
+    } else if temperature >= WARNING_C {
+        BlinkPattern::FastBlink
+    } else {
+        BlinkPattern::SlowBlink
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let led = Output::new(peripherals.GPIO8, Level::High, OutputConfig::default());
+    let mut blinker = Blinker::new(led);
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO9)
+    .with_scl(peripherals.GPIO10);
+
+    let delay = Delay::new();
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        println!("Failed to initialize AHT20: {:?}", e);
+        blinker.set(BlinkPattern::NPulses(3));
+    }
+
+    let interval = Duration::from_millis(CONFIG.sample_interval_ms as u64);
+    let mut next_read = Instant::now();
+    let mut result_at: Option<Instant> = None;
+
+    loop {
+        let now = Instant::now();
+        match result_at {
+            None if now >= next_read => {
+                next_read = now + interval;
+                match aht20.trigger(&mut i2c) {
+                    Ok(_) => {
+                        result_at = Some(now + Duration::from_millis(MEASUREMENT_TIME_MS as u64))
+                    }
+                    Err(e) => {
+                        println!("Failed to start AHT20 measurement: {:?}", e);
+                        blinker.set(BlinkPattern::NPulses(3));
+                    }
+                }
+            }
+            Some(at) if now >= at => {
+                result_at = None;
+                match aht20.fetch(&mut i2c) {
+                    Ok(Measurement { temperature, .. }) => {
+                        let pattern = pattern_for(temperature);
+                        println!("Temperature: {:.2} °C, LED: {:?}", temperature, pattern);
+                        blinker.set(pattern);
+                    }
+                    Err(e) => {
+                        println!("Failed to read data from AHT20: {:?}", e);
+                        blinker.set(BlinkPattern::NPulses(3));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        blinker.tick();
+        delay.delay_millis(5);
+    }
+}
@@ -0,0 +1,75 @@
+//! Reads a K-type thermocouple through a MAX6675 amplifier
+//!
+//! The following wiring is assumed:
+//! - SCK => GPIO4
+//! - SO (MISO) => GPIO5
+//! - CS => GPIO7
+//!
+//! The MAX6675 is read-only, so MOSI is not connected.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    spi::{
+        master::{Config as SpiConfig, Spi},
+        Mode,
+    },
+    time::Rate,
+};
+use esp_println::println;
+
+// MAX6675 frame (16 bits, MSB first):
+// D15 dummy sign bit, D14..D3 temperature, D2 thermocouple open, D1 device ID, D0 tri-state
+const OPEN_THERMOCOUPLE: u16 = 0x0004;
+const TEMPERATURE_SHIFT: u16 = 3;
+const DEGREES_PER_LSB: f32 = 0.25;
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    // MAX6675 supports up to 4.3MHz, reads on SPI mode 0
+    let config = SpiConfig::default()
+        .with_frequency(Rate::from_mhz(1))
+        .with_mode(Mode::_0);
+    let mut spi = match Spi::new(peripherals.SPI2, config) {
+        Ok(spi) => spi,
+        Err(e) => {
+            panic!("Failed to initialize SPI: {:?}", e);
+        }
+    }
+    .with_sck(peripherals.GPIO4)
+    .with_miso(peripherals.GPIO5)
+    .with_cs(peripherals.GPIO7);
+
+    loop {
+        let mut buffer = [0u8; 2];
+        match spi.read(&mut buffer) {
+            Ok(_) => {
+                let frame = u16::from_be_bytes(buffer);
+                if frame & OPEN_THERMOCOUPLE != 0 {
+                    println!("No thermocouple connected");
+                } else {
+                    // 12-bit reading, 0.25 °C per step
+                    let raw = (frame >> TEMPERATURE_SHIFT) & 0x0FFF;
+                    let temperature = raw as f32 * DEGREES_PER_LSB;
+                    println!("Thermocouple: {:.2} °C", temperature);
+                }
+            }
+            Err(e) => {
+                println!("Failed to read MAX6675: {:?}", e);
+            }
+        }
+
+        // A conversion takes up to 220ms, reading faster returns the same value
+        delay.delay_millis(500);
+    }
+}
@@ -0,0 +1,119 @@
+//! Traffic light sequencer with a pedestrian button
+//!
+//! The following wiring is assumed:
+//! - Red LED => GPIO2
+//! - Yellow LED => GPIO3
+//! - Green LED => GPIO4
+//! - Button => GPIO0 -> GND
+//!
+//! Pressing the button requests a crossing, the current (or next) green
+//! phase is then cut short, but never below MIN_GREEN_MS.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::print_banner;
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull},
+    main, time,
+};
+use esp_println::println;
+
+const DEBOUNCE_MS: u64 = 150;
+const MIN_GREEN_MS: u64 = 2000;
+
+#[derive(Clone, Copy, Debug)]
+enum Phase {
+    Red,
+    RedYellow,
+    Green,
+    Yellow,
+}
+
+impl Phase {
+    fn next(self) -> Self {
+        match self {
+            Phase::Red => Phase::RedYellow,
+            Phase::RedYellow => Phase::Green,
+            Phase::Green => Phase::Yellow,
+            Phase::Yellow => Phase::Red,
+        }
+    }
+
+    fn duration_ms(self) -> u64 {
+        match self {
+            Phase::Red => 5000,
+            Phase::RedYellow => 1500,
+            Phase::Green => 8000,
+            Phase::Yellow => 3000,
+        }
+    }
+
+    /// Levels of the (red, yellow, green) LEDs
+    fn lights(self) -> (bool, bool, bool) {
+        match self {
+            Phase::Red => (true, false, false),
+            Phase::RedYellow => (true, true, false),
+            Phase::Green => (false, false, true),
+            Phase::Yellow => (false, true, false),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    time::Instant::now().duration_since_epoch().as_millis()
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let out_config = OutputConfig::default();
+    let mut red = Output::new(peripherals.GPIO2, Level::Low, out_config);
+    let mut yellow = Output::new(peripherals.GPIO3, Level::Low, out_config);
+    let mut green = Output::new(peripherals.GPIO4, Level::Low, out_config);
+    let in_config = InputConfig::default().with_pull(Pull::Up); // Use pull-up resistor for button
+    let button = Input::new(peripherals.GPIO0, in_config);
+
+    let delay = Delay::new();
+
+    let mut phase = Phase::Red;
+    let mut phase_start = now_ms();
+    let mut last_button_change = now_ms();
+    let mut crossing_requested = false;
+
+    println!("Phase: {:?}", phase);
+
+    loop {
+        let now = now_ms();
+
+        if button.is_low() && now - last_button_change > DEBOUNCE_MS {
+            last_button_change = now;
+            if !crossing_requested {
+                println!("Pedestrian crossing requested");
+                crossing_requested = true;
+            }
+        }
+
+        let elapsed = now - phase_start;
+        let cut_short = matches!(phase, Phase::Green) && crossing_requested;
+        if elapsed >= phase.duration_ms() || (cut_short && elapsed >= MIN_GREEN_MS) {
+            if matches!(phase, Phase::Green) {
+                crossing_requested = false;
+            }
+            phase = phase.next();
+            phase_start = now;
+            println!("Phase: {:?}", phase);
+        }
+
+        let (r, y, g) = phase.lights();
+        red.set_level(Level::from(r));
+        yellow.set_level(Level::from(y));
+        green.set_level(Level::from(g));
+
+        delay.delay_millis(10);
+    }
+}
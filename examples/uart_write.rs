@@ -0,0 +1,59 @@
+//! Formats output with `write!` to an external UART and the debug console
+//!
+//! The following wiring is assumed:
+//! - UART1 TX => GPIO5 (connect to RX of a USB-UART adapter or other device)
+//! - UART1 RX => GPIO6
+//!
+//! The UART driver implements `core::fmt::Write`, and so does
+//! `esp_println::Printer`, so the same reporting code can target either port.
+
+#![no_std]
+#![no_main]
+
+use core::fmt::Write;
+
+use esp32_c3_super_mini_rust::{config::CONFIG, print_banner};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    main,
+    uart::{Config as UartConfig, Uart},
+};
+use esp_println::Printer;
+
+fn report(out: &mut impl Write, count: u32) -> core::fmt::Result {
+    writeln!(out, "Report #{}: uptime ~{} s", count, count * 2)
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let config = UartConfig::default().with_baudrate(115_200);
+    let mut uart1 = match Uart::new(peripherals.UART1, config) {
+        Ok(uart) => uart,
+        Err(e) => {
+            panic!("Failed to initialize UART1: {:?}", e);
+        }
+    }
+    .with_tx(peripherals.GPIO5)
+    .with_rx(peripherals.GPIO6);
+
+    let delay = Delay::new();
+    let mut count = 0;
+
+    loop {
+        count += 1;
+
+        // Debug console (USB-Serial-JTAG or UART0, whatever esp-println uses)
+        report(&mut Printer, count).ok();
+
+        // External device on UART1
+        if report(&mut uart1, count).is_err() {
+            writeln!(Printer, "Failed to write to UART1").ok();
+        }
+
+        delay.delay_millis(CONFIG.sample_interval_ms);
+    }
+}
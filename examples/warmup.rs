@@ -0,0 +1,162 @@
+//! Holds back AHT20 readings until the sensor has warmed up and settled
+//!
+//! The following wiring is assumed:
+//! - SDA => GPIO8
+//! - SCL => GPIO9
+//!
+//! Right after power-up the first readings drift while the sensor and the
+//! board around it reach a steady temperature. `WarmUp` flags readings as
+//! not ready until both hold:
+//! - at least `min_ms` have passed since start
+//! - the last `N` readings vary less than `max_variance`
+//!
+//! and gives up waiting after `max_ms`, so a genuinely changing environment
+//! does not keep the output suppressed forever. The same helper works for any
+//! sensor with a settling period, e.g. a pressure sensor with its IIR filter
+//! enabled.
+
+#![no_std]
+#![no_main]
+
+use esp32_c3_super_mini_rust::{
+    aht20::{Aht20, Measurement},
+    config::CONFIG,
+    print_banner,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Config as I2cConfig, I2c},
+    main,
+    time::{Duration, Instant, Rate},
+};
+use esp_println::println;
+
+const WARMUP_MIN_MS: u64 = 5_000;
+const WARMUP_MAX_MS: u64 = 60_000;
+const WARMUP_WINDOW: usize = 5;
+// In °C², 0.01 is roughly a spread of ±0.1 °C over the window
+const WARMUP_MAX_VARIANCE: f32 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WarmUpState {
+    Warming,
+    Ready,
+}
+
+struct WarmUp<const N: usize> {
+    start: Instant,
+    min: Duration,
+    max: Duration,
+    max_variance: f32,
+    window: [f32; N],
+    count: usize,
+    ready: bool,
+}
+
+impl<const N: usize> WarmUp<N> {
+    fn new(min_ms: u64, max_ms: u64, max_variance: f32) -> Self {
+        WarmUp {
+            start: Instant::now(),
+            min: Duration::from_millis(min_ms),
+            max: Duration::from_millis(max_ms),
+            max_variance,
+            window: [0.0; N],
+            count: 0,
+            ready: false,
+        }
+    }
+
+    fn variance(&self) -> Option<f32> {
+        if self.count < N {
+            return None;
+        }
+        let mean = self.window.iter().sum::<f32>() / N as f32;
+        Some(
+            self.window
+                .iter()
+                .map(|v| (v - mean) * (v - mean))
+                .sum::<f32>()
+                / N as f32,
+        )
+    }
+
+    /// Feeds one reading and reports whether readings can be trusted yet.
+    /// Once ready it stays ready.
+    fn update(&mut self, value: f32) -> WarmUpState {
+        if self.ready {
+            return WarmUpState::Ready;
+        }
+
+        self.window[self.count % N] = value;
+        self.count += 1;
+
+        let elapsed = Instant::now() - self.start;
+        let settled = self
+            .variance()
+            .is_some_and(|variance| variance < self.max_variance);
+        if (elapsed >= self.min && settled) || elapsed >= self.max {
+            self.ready = true;
+            return WarmUpState::Ready;
+        }
+        WarmUpState::Warming
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    print_banner!();
+
+    let delay = Delay::new();
+
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(CONFIG.i2c_frequency_khz));
+    let mut i2c = match I2c::new(peripherals.I2C0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(peripherals.GPIO8)
+    .with_scl(peripherals.GPIO9);
+
+    let aht20 = Aht20::new(delay);
+    if let Err(e) = aht20.start(&mut i2c) {
+        panic!("Failed to initialize AHT20: {:?}", e);
+    }
+
+    let mut warmup =
+        WarmUp::<WARMUP_WINDOW>::new(WARMUP_MIN_MS, WARMUP_MAX_MS, WARMUP_MAX_VARIANCE);
+    let mut last_state = WarmUpState::Warming;
+
+    println!("Warming up...");
+
+    loop {
+        match aht20.read(&mut i2c) {
+            Ok(Measurement {
+                temperature,
+                humidity,
+            }) => {
+                let state = warmup.update(temperature);
+                if state != last_state {
+                    println!("Sensor ready");
+                    last_state = state;
+                }
+                match state {
+                    WarmUpState::Ready => println!(
+                        "Temperature: {:.2} °C, Humidity: {:.2} %",
+                        temperature, humidity
+                    ),
+                    WarmUpState::Warming => println!(
+                        "(warming up) Temperature: {:.2} °C, variance: {:?}",
+                        temperature,
+                        warmup.variance()
+                    ),
+                }
+            }
+            Err(e) => println!("Failed to read data from AHT20: {:?}", e),
+        }
+
+        delay.delay_millis(1000);
+    }
+}
@@ -0,0 +1,508 @@
+//! Wi-Fi provisioning through an access point with a configuration page
+//!
+//! The following wiring is assumed:
+//! - Button => GPIO0 -> GND (hold during boot to forget stored credentials)
+//!
+//! Without stored credentials the board starts an open access point
+//! `esp32-c3-setup`. Connect to it and open http://192.168.2.1/, a minimal
+//! DHCP server hands out addresses from 192.168.2.2 on. There is no DNS
+//! server, so the page does not pop up by itself like a hotel login does.
+//! The SSID and password entered there are stored in flash and the board
+//! reboots. With stored credentials the access point is skipped and the
+//! board connects directly as a station.
+//!
+//! When the connection drops, reconnect attempts back off exponentially
+//! from `RECONNECT_INITIAL` to `RECONNECT_MAX`, so a node does not hammer
+//! an access point that is down but still comes back quickly after a
+//! short glitch.
+
+#![no_std]
+#![no_main]
+
+use embedded_storage::{ReadStorage, Storage};
+use esp32_c3_super_mini_rust::{
+    config::CONFIG, flash, print_banner, reconnect::ReconnectPolicy, Timeout,
+};
+use esp_backtrace as _;
+use esp_hal::{
+    clock::CpuClock,
+    delay::Delay,
+    gpio::{Input, InputConfig, Pull},
+    main,
+    rng::Rng,
+    time,
+    timer::timg::TimerGroup,
+};
+use esp_println::println;
+use esp_storage::FlashStorage;
+use esp_wifi::wifi::{
+    AccessPointConfiguration, ClientConfiguration, Configuration, WifiController,
+};
+use smoltcp::{
+    iface::{Config as IfaceConfig, Interface, SocketSet, SocketStorage},
+    socket::{tcp, udp},
+    wire::{
+        DhcpMessageType, DhcpPacket, DhcpRepr, EthernetAddress, HardwareAddress, IpAddress, IpCidr,
+        IpEndpoint, Ipv4Address,
+    },
+};
+
+const AP_SSID: &str = CONFIG.provision_ap_ssid;
+const RECONNECT_INITIAL: time::Duration = time::Duration::from_secs(1);
+const RECONNECT_MAX: time::Duration = time::Duration::from_secs(60);
+const CONNECTED_CHECK_MS: u32 = 5000;
+const CREDENTIALS_ADDR: u32 = flash::WIFI_CREDENTIALS_ADDR;
+const CREDENTIALS_MAGIC: u8 = 0xC3;
+const MAX_SSID_LEN: usize = 32;
+const MAX_PASSWORD_LEN: usize = 64;
+const RECORD_SIZE: usize = 2 + MAX_SSID_LEN + 1 + MAX_PASSWORD_LEN;
+const AP_ADDR: Ipv4Address = Ipv4Address::new(192, 168, 2, 1);
+const DHCP_LEASES: usize = 4;
+const DHCP_LEASE_SECS: u32 = 3600;
+
+const FORM_PAGE: &str = "HTTP/1.0 200 OK\r\nContent-Type: text/html\r\n\r\n\
+<html><body><h1>Wi-Fi setup</h1>\
+<form action=\"/save\" method=\"get\">\
+SSID: <input name=\"ssid\"><br>\
+Password: <input name=\"password\" type=\"password\"><br>\
+<input type=\"submit\" value=\"Save\"></form></body></html>";
+const SAVED_PAGE: &str = "HTTP/1.0 200 OK\r\nContent-Type: text/html\r\n\r\n\
+<html><body><h1>Saved, rebooting...</h1></body></html>";
+
+struct Credentials {
+    ssid: [u8; MAX_SSID_LEN],
+    ssid_len: usize,
+    password: [u8; MAX_PASSWORD_LEN],
+    password_len: usize,
+}
+
+impl Credentials {
+    fn ssid(&self) -> &str {
+        core::str::from_utf8(&self.ssid[..self.ssid_len]).unwrap_or("")
+    }
+
+    fn password(&self) -> &str {
+        core::str::from_utf8(&self.password[..self.password_len]).unwrap_or("")
+    }
+
+    /// Layout: magic, ssid_len, ssid[32], password_len, password[64]
+    fn load(flash: &mut FlashStorage) -> Option<Self> {
+        let mut bytes = [0u8; RECORD_SIZE];
+        flash.read(CREDENTIALS_ADDR, &mut bytes).ok()?;
+        // Erased flash reads as 0xFF, so a missing magic means nothing stored
+        if bytes[0] != CREDENTIALS_MAGIC {
+            return None;
+        }
+
+        let ssid_len = bytes[1] as usize;
+        let password_len = bytes[2 + MAX_SSID_LEN] as usize;
+        if ssid_len == 0 || ssid_len > MAX_SSID_LEN || password_len > MAX_PASSWORD_LEN {
+            return None;
+        }
+
+        let mut credentials = Credentials {
+            ssid: [0; MAX_SSID_LEN],
+            ssid_len,
+            password: [0; MAX_PASSWORD_LEN],
+            password_len,
+        };
+        credentials
+            .ssid
+            .copy_from_slice(&bytes[2..2 + MAX_SSID_LEN]);
+        credentials
+            .password
+            .copy_from_slice(&bytes[3 + MAX_SSID_LEN..]);
+        Some(credentials)
+    }
+
+    fn save(&self, flash: &mut FlashStorage) {
+        let mut bytes = [0xFF; RECORD_SIZE];
+        bytes[0] = CREDENTIALS_MAGIC;
+        bytes[1] = self.ssid_len as u8;
+        bytes[2..2 + MAX_SSID_LEN].copy_from_slice(&self.ssid);
+        bytes[2 + MAX_SSID_LEN] = self.password_len as u8;
+        bytes[3 + MAX_SSID_LEN..].copy_from_slice(&self.password);
+        if let Err(e) = flash.write(CREDENTIALS_ADDR, &bytes) {
+            println!("Failed to store credentials: {:?}", e);
+        }
+    }
+
+    fn erase(flash: &mut FlashStorage) {
+        if let Err(e) = flash.write(CREDENTIALS_ADDR, &[0xFF; RECORD_SIZE]) {
+            println!("Failed to erase credentials: {:?}", e);
+        }
+    }
+}
+
+/// Hands each client one address from 192.168.2.2 on, just enough for a
+/// phone or laptop to reach the configuration page
+struct DhcpServer {
+    clients: [Option<EthernetAddress>; DHCP_LEASES],
+}
+
+impl DhcpServer {
+    fn new() -> Self {
+        DhcpServer {
+            clients: [None; DHCP_LEASES],
+        }
+    }
+
+    /// Address leased to `client`, assigning a free one on first contact
+    fn lease(&mut self, client: EthernetAddress) -> Option<Ipv4Address> {
+        let index = match self.clients.iter().position(|c| *c == Some(client)) {
+            Some(index) => index,
+            None => {
+                let index = self.clients.iter().position(Option::is_none)?;
+                self.clients[index] = Some(client);
+                index
+            }
+        };
+        Some(Ipv4Address::new(192, 168, 2, 2 + index as u8))
+    }
+
+    /// Answers a discover with an offer and a request with an ack. Replies
+    /// are broadcast, the client has no address to unicast to yet.
+    fn handle(&mut self, socket: &mut udp::Socket, packet: &[u8]) {
+        let Ok(packet) = DhcpPacket::new_checked(packet) else {
+            return;
+        };
+        let Ok(request) = DhcpRepr::parse(&packet) else {
+            return;
+        };
+        let message_type = match request.message_type {
+            DhcpMessageType::Discover => DhcpMessageType::Offer,
+            // A request naming another server means the client went with it
+            DhcpMessageType::Request
+                if request.server_identifier.is_none_or(|id| id == AP_ADDR) =>
+            {
+                DhcpMessageType::Ack
+            }
+            _ => return,
+        };
+        let Some(address) = self.lease(request.client_hardware_address) else {
+            println!(
+                "DHCP: no free address for {}",
+                request.client_hardware_address
+            );
+            return;
+        };
+
+        let reply = DhcpRepr {
+            message_type,
+            transaction_id: request.transaction_id,
+            secs: 0,
+            client_hardware_address: request.client_hardware_address,
+            client_ip: Ipv4Address::UNSPECIFIED,
+            your_ip: address,
+            server_ip: AP_ADDR,
+            router: None,
+            subnet_mask: Some(Ipv4Address::new(255, 255, 255, 0)),
+            relay_agent_ip: Ipv4Address::UNSPECIFIED,
+            broadcast: request.broadcast,
+            requested_ip: None,
+            client_identifier: None,
+            server_identifier: Some(AP_ADDR),
+            parameter_request_list: None,
+            dns_servers: None,
+            max_size: None,
+            lease_duration: Some(DHCP_LEASE_SECS),
+            renew_duration: None,
+            rebind_duration: None,
+            additional_options: &[],
+        };
+        let mut buffer = [0u8; 576];
+        let len = reply.buffer_len();
+        let Some(buffer) = buffer.get_mut(..len) else {
+            return;
+        };
+        if reply
+            .emit(&mut DhcpPacket::new_unchecked(&mut buffer[..]))
+            .is_err()
+        {
+            return;
+        }
+        let to = IpEndpoint::new(IpAddress::v4(255, 255, 255, 255), 68);
+        if let Err(e) = socket.send_slice(buffer, to) {
+            println!("DHCP: failed to send reply: {:?}", e);
+        } else if message_type == DhcpMessageType::Ack {
+            println!(
+                "DHCP: {} leased to {}",
+                address, request.client_hardware_address
+            );
+        }
+    }
+}
+
+/// Decodes a form value (`+` is a space, `%XX` a hex escape) into `dst`,
+/// returns the decoded length or `None` if it does not fit
+fn url_decode(src: &str, dst: &mut [u8]) -> Option<usize> {
+    let src = src.as_bytes();
+    let mut i = 0;
+    let mut len = 0;
+    while i < src.len() {
+        let byte = match src[i] {
+            b'+' => b' ',
+            b'%' if i + 3 <= src.len() => {
+                let hex = &src[i + 1..i + 3];
+                // from_str_radix accepts a leading sign, so "%+F" would
+                // otherwise decode to 0x0F
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return None;
+                }
+                let hex = core::str::from_utf8(hex).ok()?;
+                i += 2;
+                u8::from_str_radix(hex, 16).ok()?
+            }
+            b => b,
+        };
+        *dst.get_mut(len)? = byte;
+        len += 1;
+        i += 1;
+    }
+    Some(len)
+}
+
+/// Extracts the value of `key` from a query string like `a=1&b=2`
+fn query_value<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then_some(v)
+    })
+}
+
+/// Parses `GET /save?ssid=...&password=... HTTP/1.1`
+fn parse_save_request(request: &str) -> Option<Credentials> {
+    let path = request.strip_prefix("GET ")?.split(' ').next()?;
+    let query = path.strip_prefix("/save?")?;
+
+    let mut credentials = Credentials {
+        ssid: [0; MAX_SSID_LEN],
+        ssid_len: 0,
+        password: [0; MAX_PASSWORD_LEN],
+        password_len: 0,
+    };
+    credentials.ssid_len = url_decode(query_value(query, "ssid")?, &mut credentials.ssid)?;
+    credentials.password_len = url_decode(
+        query_value(query, "password").unwrap_or(""),
+        &mut credentials.password,
+    )?;
+
+    (credentials.ssid_len > 0).then_some(credentials)
+}
+
+fn timestamp() -> smoltcp::time::Instant {
+    smoltcp::time::Instant::from_micros(
+        time::Instant::now().duration_since_epoch().as_micros() as i64
+    )
+}
+
+/// Connects as a station and keeps reconnecting. Returns only if the stored
+/// credentials cannot be turned into a configuration, so the caller can fall
+/// back to the access point.
+fn connect_station(controller: &mut WifiController<'_>, credentials: &Credentials) {
+    println!("Connecting to {}", credentials.ssid());
+    let (Ok(ssid), Ok(password)) = (
+        credentials.ssid().try_into(),
+        credentials.password().try_into(),
+    ) else {
+        println!("Stored credentials do not fit the Wi-Fi configuration");
+        return;
+    };
+    let config = Configuration::Client(ClientConfiguration {
+        ssid,
+        password,
+        ..Default::default()
+    });
+
+    let delay = Delay::new();
+    let mut policy = ReconnectPolicy::new(RECONNECT_INITIAL, RECONNECT_MAX);
+    let mut started = false;
+    let mut connected = false;
+    loop {
+        // A failed start is retried with the same back-off as a failed
+        // connect
+        if !started {
+            match controller
+                .set_configuration(&config)
+                .and_then(|_| controller.start())
+            {
+                Ok(()) => started = true,
+                Err(e) => {
+                    let wait = policy.next_delay();
+                    println!(
+                        "Failed to start station: {:?}, next attempt in {} ms",
+                        e,
+                        wait.as_millis()
+                    );
+                    delay.delay_millis(wait.as_millis() as u32);
+                    continue;
+                }
+            }
+        }
+
+        if let Ok(true) = controller.is_connected() {
+            if !connected {
+                println!(
+                    "Connected to {} after {} attempt(s)",
+                    credentials.ssid(),
+                    policy.attempts()
+                );
+                connected = true;
+                policy.reset();
+            }
+            delay.delay_millis(CONNECTED_CHECK_MS);
+            continue;
+        }
+
+        if connected {
+            println!("Connection to {} lost", credentials.ssid());
+            connected = false;
+        }
+        if let Err(e) = controller.connect() {
+            println!("Failed to connect: {:?}", e);
+        }
+        let wait = policy.next_delay();
+        println!("Not connected, next attempt in {} ms", wait.as_millis());
+        delay.delay_millis(wait.as_millis() as u32);
+    }
+}
+
+#[main]
+fn main() -> ! {
+    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
+    let peripherals = esp_hal::init(config);
+    print_banner!();
+
+    esp_alloc::heap_allocator!(72 * 1024);
+
+    let mut flash = FlashStorage::new();
+
+    let in_config = InputConfig::default().with_pull(Pull::Up);
+    let button = Input::new(peripherals.GPIO0, in_config);
+    if button.is_low() {
+        println!("Button held, forgetting stored credentials");
+        Credentials::erase(&mut flash);
+    }
+
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    let esp_wifi_ctrl = match esp_wifi::init(
+        timg0.timer0,
+        Rng::new(peripherals.RNG),
+        peripherals.RADIO_CLK,
+    ) {
+        Ok(ctrl) => ctrl,
+        Err(e) => {
+            panic!("Failed to initialize Wi-Fi: {:?}", e);
+        }
+    };
+    let (mut controller, interfaces) = match esp_wifi::wifi::new(&esp_wifi_ctrl, peripherals.WIFI) {
+        Ok(wifi) => wifi,
+        Err(e) => {
+            panic!("Failed to create Wi-Fi controller: {:?}", e);
+        }
+    };
+
+    if let Some(credentials) = Credentials::load(&mut flash) {
+        connect_station(&mut controller, &credentials);
+        println!("Starting access point {} to enter new credentials", AP_SSID);
+    } else {
+        println!("No credentials stored, starting access point {}", AP_SSID);
+    }
+
+    let ap_config = Configuration::AccessPoint(AccessPointConfiguration {
+        ssid: AP_SSID.try_into().unwrap(),
+        ..Default::default()
+    });
+    controller.set_configuration(&ap_config).unwrap();
+    controller.start().unwrap();
+
+    let mut device = interfaces.ap;
+    let hw_addr = HardwareAddress::Ethernet(EthernetAddress::from_bytes(&device.mac_address()));
+    let mut iface = Interface::new(IfaceConfig::new(hw_addr), &mut device, timestamp());
+    iface.update_ip_addrs(|addrs| {
+        addrs
+            .push(IpCidr::new(IpAddress::Ipv4(AP_ADDR), 24))
+            .unwrap();
+    });
+
+    let mut socket_storage: [SocketStorage; 2] = Default::default();
+    let mut sockets = SocketSet::new(&mut socket_storage[..]);
+    let mut rx_buffer = [0u8; 1024];
+    let mut tx_buffer = [0u8; 1024];
+    let socket = tcp::Socket::new(
+        tcp::SocketBuffer::new(&mut rx_buffer[..]),
+        tcp::SocketBuffer::new(&mut tx_buffer[..]),
+    );
+    let handle = sockets.add(socket);
+
+    let mut dhcp_rx_meta = [udp::PacketMetadata::EMPTY; 4];
+    let mut dhcp_rx_buffer = [0u8; 1536];
+    let mut dhcp_tx_meta = [udp::PacketMetadata::EMPTY; 4];
+    let mut dhcp_tx_buffer = [0u8; 1536];
+    let mut dhcp_socket = udp::Socket::new(
+        udp::PacketBuffer::new(&mut dhcp_rx_meta[..], &mut dhcp_rx_buffer[..]),
+        udp::PacketBuffer::new(&mut dhcp_tx_meta[..], &mut dhcp_tx_buffer[..]),
+    );
+    dhcp_socket.bind(67).unwrap();
+    let dhcp_handle = sockets.add(dhcp_socket);
+    let mut dhcp = DhcpServer::new();
+
+    println!("Open http://192.168.2.1/");
+
+    let delay = Delay::new();
+    let mut request = [0u8; 512];
+    let mut request_len = 0;
+    let mut packet = [0u8; 576];
+
+    loop {
+        iface.poll(timestamp(), &mut device, &mut sockets);
+
+        let dhcp_socket = sockets.get_mut::<udp::Socket>(dhcp_handle);
+        while let Ok((len, _)) = dhcp_socket.recv_slice(&mut packet) {
+            dhcp.handle(dhcp_socket, &packet[..len]);
+        }
+
+        let socket = sockets.get_mut::<tcp::Socket>(handle);
+
+        if !socket.is_open() {
+            socket.listen(80).unwrap();
+            request_len = 0;
+        }
+
+        if socket.can_recv() {
+            request_len += socket.recv_slice(&mut request[request_len..]).unwrap_or(0);
+            // A request can arrive in several segments, wait for the blank
+            // line ending the header before parsing. A header too long for
+            // the buffer is answered with the form page.
+            let complete = request[..request_len].windows(4).any(|w| w == b"\r\n\r\n");
+            if !complete && request_len < request.len() {
+                delay.delay_millis(1);
+                continue;
+            }
+            let text = core::str::from_utf8(&request[..request_len]).unwrap_or("");
+            request_len = 0;
+
+            match parse_save_request(text) {
+                Some(credentials) => {
+                    println!("Received credentials for {}", credentials.ssid());
+                    credentials.save(&mut flash);
+                    socket.send_slice(SAVED_PAGE.as_bytes()).ok();
+                    socket.close();
+
+                    // Let the response go out before rebooting
+                    let timeout = Timeout::from_millis(1000);
+                    while !timeout.expired() {
+                        iface.poll(timestamp(), &mut device, &mut sockets);
+                    }
+                    esp_hal::system::software_reset();
+                }
+                None => {
+                    socket.send_slice(FORM_PAGE.as_bytes()).ok();
+                    socket.close();
+                }
+            }
+        }
+
+        delay.delay_millis(1);
+    }
+}
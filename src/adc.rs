@@ -0,0 +1,23 @@
+//! Oversampling for the ADC examples
+//!
+//! A single ADC read is noisy by a few counts. Both helpers take the read as
+//! a closure, so they work with any pin, attenuation and calibration scheme:
+//!
+//! ```ignore
+//! let raw = adc::read_oversampled(|| nb::block!(adc1.read_oneshot(&mut pin)).unwrap(), 16);
+//! ```
+
+/// Average of `k` reads, still in 12-bit counts
+pub fn read_oversampled(mut read: impl FnMut() -> u16, k: u32) -> u16 {
+    let sum: u32 = (0..k.max(1)).map(|_| read() as u32).sum();
+    (sum / k.max(1)) as u16
+}
+
+/// Oversampling and decimation: sums `4^extra_bits` reads and shifts right
+/// by `extra_bits`, giving a `12 + extra_bits` bit result. Capped at 4 extra
+/// bits (256 reads) so the result still fits in a u16.
+pub fn read_decimated(mut read: impl FnMut() -> u16, extra_bits: u32) -> u16 {
+    let extra_bits = extra_bits.min(4);
+    let sum: u32 = (0..1u32 << (2 * extra_bits)).map(|_| read() as u32).sum();
+    (sum >> extra_bits) as u16
+}
@@ -0,0 +1,452 @@
+//! AHT20 temperature and humidity sensor
+//!
+//! The driver does not own the bus, every call takes the `I2c` to use. That
+//! way the sensor can share the bus with other devices (an EEPROM, a
+//! display, a multiplexer with several AHT20s behind it):
+//!
+//! ```ignore
+//! let aht20 = Aht20::new(Delay::new());
+//! aht20.start(&mut i2c)?;
+//! let Measurement { temperature, humidity } = aht20.read(&mut i2c)?;
+//! ```
+//!
+//! `start` runs the power-on handshake from the datasheet (calibration bit
+//! check, init command only when needed). Until it has succeeded every
+//! measurement fails with `NotStarted`, an uncalibrated sensor returns
+//! plausible looking but wrong values. Reads poll the busy bit and check the
+//! CRC byte, so a broken transfer is reported instead of decoded.
+
+use core::cell::Cell;
+
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{AcknowledgeCheckFailedReason, Error as I2cError, I2c},
+    Blocking,
+};
+use esp_println::println;
+
+use crate::Timeout;
+
+pub use aht20_core::{crc8, is_calibrated, FrameError, Measurement};
+
+/// Fixed I2C address of the AHT20
+pub const ADDR: u8 = 0x38;
+
+const CMD_INIT: u8 = 0xBE; // Initialize command
+const CMD_MEASURE: u8 = 0xAC; // Trigger measurement command
+const CMD_STATUS: u8 = 0x71; // Read status word command
+const CMD_SOFT_RESET: u8 = 0xBA; // Soft reset command
+const INIT_PARAM1: u8 = 0x08;
+const INIT_PARAM2: u8 = 0x00;
+const MEASURE_PARAM1: u8 = 0x33;
+const MEASURE_PARAM2: u8 = 0x00;
+const BUSY_TIMEOUT_MS: u64 = 100; // How long to keep polling a busy measurement
+
+/// Time from `trigger` until the result can be fetched
+pub const MEASUREMENT_TIME_MS: u32 = 80;
+
+// Reads that fail part way through (data NACK, CRC mismatch, ...) are retried this many times
+const READ_RETRIES: u32 = 2;
+
+#[derive(Debug)]
+pub enum Aht20Error {
+    I2c(I2cError),
+    /// Measurement attempted before `start` succeeded
+    NotStarted,
+    /// Calibration-enable bit still clear after sending the init command
+    NotCalibrated(u8),
+    /// Measurement did not finish in time
+    Busy,
+    /// Values outside of the sensor's range, usually a corrupted transfer
+    InvalidData {
+        temperature: f32,
+        humidity: f32,
+    },
+    /// CRC byte does not match the data, the transfer was cut short or garbled
+    Crc {
+        expected: u8,
+        received: u8,
+    },
+}
+
+impl Aht20Error {
+    /// True for errors where the sensor answered but the transfer went wrong
+    /// part way through, so trying again is likely to work. A NACK on the
+    /// address means nobody answered at all and is not worth retrying.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Aht20Error::I2c(I2cError::AcknowledgeCheckFailed(
+                AcknowledgeCheckFailedReason::Address,
+            )) => false,
+            Aht20Error::I2c(_) | Aht20Error::Crc { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<I2cError> for Aht20Error {
+    fn from(e: I2cError) -> Self {
+        Aht20Error::I2c(e)
+    }
+}
+
+impl From<FrameError> for Aht20Error {
+    fn from(e: FrameError) -> Self {
+        match e {
+            FrameError::Busy => Aht20Error::Busy,
+            FrameError::InvalidData {
+                temperature,
+                humidity,
+            } => Aht20Error::InvalidData {
+                temperature,
+                humidity,
+            },
+            FrameError::Crc { expected, received } => Aht20Error::Crc { expected, received },
+        }
+    }
+}
+
+/// Mean of several samples with its uncertainty
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    pub mean: f32,
+    /// Standard deviation divided by sqrt(n)
+    pub std_error: f32,
+    /// Half-width of the 95% confidence interval: the true mean is within
+    /// `mean ± ci95` with 95% confidence
+    pub ci95: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AveragedMeasurement {
+    pub temperature: Estimate,
+    pub humidity: Estimate,
+    pub samples: u32,
+}
+
+/// Running mean and variance (Welford's method, stable in f32)
+pub struct Stats {
+    count: u32,
+    mean: f32,
+    m2: f32,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Stats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, sample: f32) {
+        self.count += 1;
+        let delta = sample - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (sample - self.mean);
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn estimate(&self) -> Estimate {
+        let variance = if self.count > 1 {
+            self.m2 / (self.count - 1) as f32
+        } else {
+            0.0
+        };
+        let std_error = sqrt(variance / self.count.max(1) as f32);
+        Estimate {
+            mean: self.mean,
+            std_error,
+            ci95: std_error * t95(self.count.saturating_sub(1)),
+        }
+    }
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Stats::new()
+    }
+}
+
+/// Turns the 7 byte measurement frame (status, 5 data bytes, CRC) into a
+/// measurement. Fails with `Busy` while the measurement is still running.
+pub fn decode(frame: &[u8; 7]) -> Result<Measurement, Aht20Error> {
+    Ok(aht20_core::decode(frame)?)
+}
+
+/// Middle of three values, a lone outlier never makes it through
+fn median3(a: f32, b: f32, c: f32) -> f32 {
+    a.min(b).max(a.max(b).min(c))
+}
+
+/// Square root by Newton's method, there is no `f32::sqrt` without std
+fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    // Halving the exponent bits gives a guess within a few percent
+    let mut y = f32::from_bits((x.to_bits() >> 1) + 0x1FC0_0000);
+    for _ in 0..4 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+/// Two-sided 95% quantile of Student's t distribution for `df` degrees of
+/// freedom. Above 30 the normal value 1.96 is close enough.
+fn t95(df: u32) -> f32 {
+    const TABLE: [f32; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+    match df {
+        0 => f32::INFINITY,
+        1..=30 => TABLE[df as usize - 1],
+        _ => 1.96,
+    }
+}
+
+/// Formats bytes as space separated hex in brackets, for the I2C trace
+struct Hex<'a>(&'a [u8]);
+
+impl core::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{:02X}", byte)?;
+        }
+        write!(f, "]")
+    }
+}
+
+pub struct Aht20 {
+    delay: Delay,
+    trace: bool,
+    reset_on_init: bool,
+    started: Cell<bool>,
+}
+
+impl Aht20 {
+    /// Does not touch the bus yet, call `start` before the first measurement
+    pub fn new(delay: Delay) -> Self {
+        Aht20 {
+            delay,
+            trace: false,
+            reset_on_init: false,
+            started: Cell::new(false),
+        }
+    }
+
+    /// Prints every I2C transfer as `W 0x38 [AC 33 00]` / `R 0x38 -> [1C ...]`
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Sends a soft reset in `start` before the handshake, some AHT20 units
+    /// only work reliably after one
+    pub fn with_reset_on_init(mut self, reset_on_init: bool) -> Self {
+        self.reset_on_init = reset_on_init;
+        self
+    }
+
+    /// Runs the power-on handshake from the datasheet: wait at least 40 ms,
+    /// read the status word and only send the init/calibration command when
+    /// the calibration-enable bit is not set yet. Once this returns, the first
+    /// reading is already valid.
+    pub fn start(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), Aht20Error> {
+        // Sensor needs at least 40ms after power-on before it accepts commands
+        self.delay.delay_millis(40);
+
+        if self.reset_on_init {
+            self.soft_reset(i2c)?;
+        }
+
+        self.init(i2c)?;
+        self.started.set(true);
+        Ok(())
+    }
+
+    /// Restarts the sensor without a power cycle, the calibration bit is
+    /// kept. Follow it with `init`.
+    pub fn soft_reset(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), Aht20Error> {
+        println!("Resetting AHT20 sensor...");
+        self.write(i2c, &[CMD_SOFT_RESET])?;
+        // Soft reset takes less than 20ms
+        self.delay.delay_millis(20);
+        Ok(())
+    }
+
+    /// Checks the calibration-enable bit and calibrates if needed. Also used
+    /// to re-detect the sensor after it was unplugged.
+    pub fn init(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), Aht20Error> {
+        if !is_calibrated(self.status(i2c)?) {
+            println!("AHT20 not calibrated, sending init command...");
+            self.write(i2c, &[CMD_INIT, INIT_PARAM1, INIT_PARAM2])?;
+
+            // Datasheet asks for 10ms after the init command
+            self.delay.delay_millis(10);
+
+            let status = self.status(i2c)?;
+            if !is_calibrated(status) {
+                return Err(Aht20Error::NotCalibrated(status));
+            }
+        }
+
+        Ok(())
+    }
+
+    // All bus access goes through these three, so the trace sees every transfer
+
+    fn check_started(&self) -> Result<(), Aht20Error> {
+        if self.started.get() {
+            Ok(())
+        } else {
+            Err(Aht20Error::NotStarted)
+        }
+    }
+
+    fn write(&self, i2c: &mut I2c<'_, Blocking>, bytes: &[u8]) -> Result<(), I2cError> {
+        let result = i2c.write(ADDR, bytes);
+        if self.trace {
+            match &result {
+                Ok(_) => println!("W 0x{:02X} {}", ADDR, Hex(bytes)),
+                Err(e) => println!("W 0x{:02X} {} failed: {:?}", ADDR, Hex(bytes), e),
+            }
+        }
+        result
+    }
+
+    fn read_bytes(&self, i2c: &mut I2c<'_, Blocking>, buffer: &mut [u8]) -> Result<(), I2cError> {
+        let result = i2c.read(ADDR, buffer);
+        if self.trace {
+            match &result {
+                Ok(_) => println!("R 0x{:02X} -> {}", ADDR, Hex(buffer)),
+                Err(e) => println!("R 0x{:02X} failed: {:?}", ADDR, e),
+            }
+        }
+        result
+    }
+
+    fn write_read(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), I2cError> {
+        let result = i2c.write_read(ADDR, bytes, buffer);
+        if self.trace {
+            match &result {
+                Ok(_) => println!("W 0x{:02X} {} R -> {}", ADDR, Hex(bytes), Hex(buffer)),
+                Err(e) => println!("W 0x{:02X} {} R failed: {:?}", ADDR, Hex(bytes), e),
+            }
+        }
+        result
+    }
+
+    pub fn status(&self, i2c: &mut I2c<'_, Blocking>) -> Result<u8, Aht20Error> {
+        let mut status = [0u8];
+        self.write_read(i2c, &[CMD_STATUS], &mut status)?;
+        Ok(status[0])
+    }
+
+    /// Reads a measurement, retrying up to `READ_RETRIES` times when a
+    /// transfer fails part way through
+    pub fn read(&self, i2c: &mut I2c<'_, Blocking>) -> Result<Measurement, Aht20Error> {
+        aht20_core::retry(
+            READ_RETRIES,
+            || self.read_once(i2c),
+            |e, retry| {
+                if self.trace && e.is_transient() {
+                    println!("AHT20 partial read ({:?}), retry {}", e, retry);
+                }
+                e.is_transient()
+            },
+        )
+    }
+
+    /// One measurement without retries, blocks for `MEASUREMENT_TIME_MS`
+    pub fn read_once(&self, i2c: &mut I2c<'_, Blocking>) -> Result<Measurement, Aht20Error> {
+        self.trigger(i2c)?;
+        self.delay.delay_millis(MEASUREMENT_TIME_MS);
+        self.fetch(i2c)
+    }
+
+    /// Starts a measurement. Wait `MEASUREMENT_TIME_MS` before `fetch`, an
+    /// async caller can await a timer in between instead of blocking.
+    pub fn trigger(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), Aht20Error> {
+        self.check_started()?;
+        self.write(i2c, &[CMD_MEASURE, MEASURE_PARAM1, MEASURE_PARAM2])?;
+        Ok(())
+    }
+
+    /// Reads the result of the last `trigger`, polling while the sensor
+    /// reports busy
+    pub fn fetch(&self, i2c: &mut I2c<'_, Blocking>) -> Result<Measurement, Aht20Error> {
+        let mut frame = [0u8; 7];
+        self.fetch_raw(i2c, &mut frame)?;
+        decode(&frame)
+    }
+
+    /// Like `fetch`, but leaves the raw frame undecoded, see `decode`
+    pub fn fetch_raw(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        frame: &mut [u8; 7],
+    ) -> Result<(), Aht20Error> {
+        self.check_started()?;
+        let timeout = Timeout::from_millis(BUSY_TIMEOUT_MS);
+        loop {
+            self.read_bytes(i2c, frame)?;
+            if !aht20_core::is_busy(frame[0]) {
+                return Ok(());
+            }
+            if timeout.expired() {
+                return Err(Aht20Error::Busy);
+            }
+            self.delay.delay_millis(10);
+        }
+    }
+
+    /// Three back-to-back readings, each value is the median of the three
+    pub fn read_median3(&self, i2c: &mut I2c<'_, Blocking>) -> Result<Measurement, Aht20Error> {
+        let a = self.read(i2c)?;
+        let b = self.read(i2c)?;
+        let c = self.read(i2c)?;
+        Ok(Measurement {
+            temperature: median3(a.temperature, b.temperature, c.temperature),
+            humidity: median3(a.humidity, b.humidity, c.humidity),
+        })
+    }
+
+    /// Takes `samples` readings back to back and returns their means with
+    /// standard errors and 95% confidence intervals. At least two readings
+    /// are taken, one has no spread to estimate. Fails on the first reading
+    /// that fails, a partial average would hide the problem.
+    pub fn measure_averaged(
+        &self,
+        i2c: &mut I2c<'_, Blocking>,
+        samples: u32,
+    ) -> Result<AveragedMeasurement, Aht20Error> {
+        let mut temperature = Stats::new();
+        let mut humidity = Stats::new();
+        for _ in 0..samples.max(2) {
+            let m = self.read(i2c)?;
+            temperature.add(m.temperature);
+            humidity.add(m.humidity);
+        }
+        Ok(AveragedMeasurement {
+            temperature: temperature.estimate(),
+            humidity: humidity.estimate(),
+            samples: temperature.count(),
+        })
+    }
+}
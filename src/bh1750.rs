@@ -0,0 +1,97 @@
+//! BH1750 ambient light sensor
+//!
+//! Like the AHT20 driver this one does not own the bus, every call takes the
+//! `I2c` to use:
+//!
+//! ```ignore
+//! let bh1750 = Bh1750::new(Delay::new());
+//! bh1750.start(&mut i2c)?;
+//! let lux = bh1750.read_lux(&mut i2c)?;
+//! ```
+//!
+//! `read_lux` uses the one-time high resolution mode: the sensor wakes up,
+//! measures once with 1 lx resolution and powers down again on its own, so
+//! it only draws current while measuring. Code that cannot block for the
+//! measurement time calls `trigger` and `fetch` separately, code that reads
+//! often switches to `start_continuous` and only calls `fetch`.
+
+use esp_hal::{
+    delay::Delay,
+    i2c::master::{Error as I2cError, I2c},
+    Blocking,
+};
+
+/// I2C address with the ADDR pin low
+pub const ADDR: u8 = 0x23;
+/// I2C address with the ADDR pin tied to 3V3
+pub const ADDR_HIGH: u8 = 0x5C;
+
+const CMD_POWER_ON: u8 = 0x01; // Wake up and wait for a measurement command
+const CMD_RESET: u8 = 0x07; // Clear the data register, only accepted while powered on
+const CMD_CONTINUOUS_H_RES: u8 = 0x10; // Measure at 1 lx resolution until powered down
+const CMD_ONE_TIME_H_RES: u8 = 0x20; // Single 1 lx measurement, powers down afterwards
+
+/// Time from `trigger` until the result can be fetched. High resolution mode
+/// takes 120ms typical, 180ms max.
+pub const MEASUREMENT_TIME_MS: u32 = 180;
+
+/// Converts the raw 16-bit result to lux
+pub fn lux(raw: u16) -> f32 {
+    // Datasheet: lux = count / 1.2 at the default measurement time
+    raw as f32 / 1.2
+}
+
+pub struct Bh1750 {
+    addr: u8,
+    delay: Delay,
+}
+
+impl Bh1750 {
+    pub fn new(delay: Delay) -> Self {
+        Bh1750 { addr: ADDR, delay }
+    }
+
+    /// Talks to the sensor at `addr` instead of `ADDR`, see `ADDR_HIGH`
+    pub fn with_address(mut self, addr: u8) -> Self {
+        self.addr = addr;
+        self
+    }
+
+    pub fn addr(&self) -> u8 {
+        self.addr
+    }
+
+    /// Powers the sensor on and clears the data register
+    pub fn start(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), I2cError> {
+        i2c.write(self.addr, &[CMD_POWER_ON])?;
+        i2c.write(self.addr, &[CMD_RESET])
+    }
+
+    /// Switches to continuous high resolution mode, after the first
+    /// `MEASUREMENT_TIME_MS` every `fetch` returns the latest result
+    pub fn start_continuous(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), I2cError> {
+        i2c.write(self.addr, &[CMD_CONTINUOUS_H_RES])
+    }
+
+    /// Starts a one-time high resolution measurement, the result is ready
+    /// `MEASUREMENT_TIME_MS` later
+    pub fn trigger(&self, i2c: &mut I2c<'_, Blocking>) -> Result<(), I2cError> {
+        i2c.write(self.addr, &[CMD_ONE_TIME_H_RES])
+    }
+
+    /// Reads the result of the last measurement in lux
+    pub fn fetch(&self, i2c: &mut I2c<'_, Blocking>) -> Result<f32, I2cError> {
+        // Result is 16 bits, MSB first
+        let mut buffer = [0u8; 2];
+        i2c.read(self.addr, &mut buffer)?;
+        Ok(lux(u16::from_be_bytes(buffer)))
+    }
+
+    /// Triggers a one-time measurement, waits for it and returns the
+    /// illuminance in lux
+    pub fn read_lux(&self, i2c: &mut I2c<'_, Blocking>) -> Result<f32, I2cError> {
+        self.trigger(i2c)?;
+        self.delay.delay_millis(MEASUREMENT_TIME_MS);
+        self.fetch(i2c)
+    }
+}
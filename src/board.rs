@@ -0,0 +1,73 @@
+//! Pin plan of the super mini and a guard against pins used twice
+//!
+//! esp-hal already refuses to hand out the same GPIO twice, but only as a
+//! "use of moved value" compile error deep in `main`, or not at all once
+//! pins are picked by number at runtime. `claim_pin` records which
+//! subsystem uses which GPIO during setup and panics with both names on a
+//! conflict. `pins_unique` does the same check at compile time for a fixed
+//! pin list.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+/// GPIO0..GPIO21
+pub const NUM_GPIOS: usize = 22;
+
+// Pins the examples use by default
+pub const BUTTON: u8 = 0;
+pub const LED_STRIP: u8 = 4;
+/// Onboard LED, lit when low
+pub const LED: u8 = 8;
+pub const I2C_SDA: u8 = 8;
+pub const I2C_SCL: u8 = 9;
+
+static CLAIMS: Mutex<RefCell<[Option<&'static str>; NUM_GPIOS]>> =
+    Mutex::new(RefCell::new([None; NUM_GPIOS]));
+
+/// Records that `owner` uses `gpio`. Panics if the pin does not exist or
+/// somebody else claimed it already.
+pub fn claim_pin(gpio: u8, owner: &'static str) {
+    critical_section::with(|cs| {
+        let mut claims = CLAIMS.borrow_ref_mut(cs);
+        let Some(slot) = claims.get_mut(gpio as usize) else {
+            panic!("{} wants GPIO{}, which does not exist", owner, gpio);
+        };
+        if let Some(previous) = slot {
+            panic!(
+                "GPIO{} is used by both {} and {}, move one of them to another pin",
+                gpio, previous, owner
+            );
+        }
+        *slot = Some(owner);
+    });
+}
+
+/// Gives a pin back, e.g. after a subsystem was shut down
+pub fn release_pin(gpio: u8) {
+    critical_section::with(|cs| {
+        if let Some(slot) = CLAIMS.borrow_ref_mut(cs).get_mut(gpio as usize) {
+            *slot = None;
+        }
+    });
+}
+
+/// True when no pin appears twice, for compile-time checks:
+///
+/// ```ignore
+/// const _: () = assert!(board::pins_unique(&[board::LED, board::I2C_SDA]));
+/// ```
+pub const fn pins_unique(pins: &[u8]) -> bool {
+    let mut i = 0;
+    while i < pins.len() {
+        let mut j = i + 1;
+        while j < pins.len() {
+            if pins[i] == pins[j] {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
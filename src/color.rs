@@ -0,0 +1,58 @@
+//! Color type shared by the LED examples
+//!
+//! RGBW to match the sk6812 strips, plain RGB LEDs leave `w` at 0 and
+//! ignore it. `Strip::flush` takes (r, g, b, w) tuples, see `to_rgbw`.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub w: u8,
+}
+
+impl Color {
+    pub const OFF: Color = Color::new(0, 0, 0, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8, w: u8) -> Self {
+        Color { r, g, b, w }
+    }
+
+    /// Color without the white channel
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color::new(r, g, b, 0)
+    }
+
+    pub const fn to_rgbw(self) -> (u8, u8, u8, u8) {
+        (self.r, self.g, self.b, self.w)
+    }
+
+    /// Fully saturated color at position `pos` around the color wheel
+    pub fn wheel(pos: u8) -> Self {
+        match pos {
+            0..=84 => Color::rgb(255 - pos * 3, pos * 3, 0),
+            85..=169 => {
+                let pos = pos - 85;
+                Color::rgb(0, 255 - pos * 3, pos * 3)
+            }
+            _ => {
+                let pos = pos - 170;
+                Color::rgb(pos * 3, 0, 255 - pos * 3)
+            }
+        }
+    }
+
+    /// Blends towards `other`, `t` goes from 0 (all `self`) to 255 (almost all `other`)
+    pub fn lerp(self, other: Color, t: u8) -> Color {
+        let mix = |a: u8, b: u8| {
+            let (a, b, t) = (a as i32, b as i32, t as i32);
+            (a + (b - a) * t / 256) as u8
+        };
+        Color::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.w, other.w),
+        )
+    }
+}
@@ -0,0 +1,67 @@
+//! Build-time settings shared by the examples
+//!
+//! Each field can be overridden when building, with an environment variable
+//! named after it in upper case with an `APP_` prefix:
+//!
+//! ```text
+//! APP_I2C_FREQUENCY_KHZ=100 APP_NUM_LEDS=30 cargo espflash flash --release --example aht20
+//! ```
+//!
+//! Values are parsed at compile time, a malformed number fails the build
+//! rather than the firmware, and cargo rebuilds when one of the variables
+//! changes. Pins are not in here: every `peripherals.GPIOx` is its own type,
+//! so which pin an example uses is decided by the code (see `board`).
+
+/// All tunables, see `CONFIG` for the defaults
+pub struct Config {
+    /// I2C bus clock in kHz. 400 suits short wires, drop to 100 for long
+    /// wires or weak pull-ups. `APP_I2C_FREQUENCY_KHZ`, default 400.
+    pub i2c_frequency_khz: u32,
+    /// Number of LEDs on the sk6812 strip. `APP_NUM_LEDS`, default 8.
+    pub num_leds: usize,
+    /// Time between two sensor readings in ms. `APP_SAMPLE_INTERVAL_MS`,
+    /// default 2000.
+    pub sample_interval_ms: u32,
+    /// Name of the access point the board opens while it has no WiFi
+    /// credentials. `APP_PROVISION_AP_SSID`, default `esp32-c3-setup`.
+    pub provision_ap_ssid: &'static str,
+}
+
+pub const CONFIG: Config = Config {
+    i2c_frequency_khz: parse_u32(option_env!("APP_I2C_FREQUENCY_KHZ"), 400),
+    num_leds: parse_u32(option_env!("APP_NUM_LEDS"), 8) as usize,
+    sample_interval_ms: parse_u32(option_env!("APP_SAMPLE_INTERVAL_MS"), 2000),
+    provision_ap_ssid: match option_env!("APP_PROVISION_AP_SSID") {
+        Some(ssid) => ssid,
+        None => "esp32-c3-setup",
+    },
+};
+
+/// Decimal number from an environment variable, `default` when it is unset
+const fn parse_u32(value: Option<&str>, default: u32) -> u32 {
+    let bytes = match value {
+        Some(value) => value.as_bytes(),
+        None => return default,
+    };
+    if bytes.is_empty() {
+        panic!("empty number in an APP_* environment variable");
+    }
+
+    let mut result: u32 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let digit = bytes[i];
+        if !digit.is_ascii_digit() {
+            panic!("invalid number in an APP_* environment variable");
+        }
+        result = match result.checked_mul(10) {
+            Some(r) => match r.checked_add((digit - b'0') as u32) {
+                Some(r) => r,
+                None => panic!("number too large in an APP_* environment variable"),
+            },
+            None => panic!("number too large in an APP_* environment variable"),
+        };
+        i += 1;
+    }
+    result
+}
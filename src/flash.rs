@@ -0,0 +1,13 @@
+//! Flash offsets of the records the examples store
+//!
+//! All of them live in the NVS partition of the default partition table
+//! (0x9000, 24 KB), which nothing else uses since the examples do not run
+//! the NVS library. Each record gets its own 4 KB sector, so erasing one
+//! never touches another.
+
+/// Versioned configuration of the flash_config example
+pub const CONFIG_ADDR: u32 = 0x9000;
+/// Wi-Fi credentials of the wifi_provision example
+pub const WIFI_CREDENTIALS_ADDR: u32 = 0xA000;
+/// Sensor calibration of the soil_moisture example
+pub const SOIL_CALIBRATION_ADDR: u32 = 0xB000;
@@ -0,0 +1,36 @@
+//! Sharp GP2Y0A21YK0F, 10-80cm IR distance sensor
+//!
+//! The analog output falls non-linearly with distance. `millivolts_to_cm`
+//! turns a voltage into centimeters by interpolating the datasheet curve:
+//!
+//! ```ignore
+//! let cm = gp2y0a21::millivolts_to_cm(raw as u32 * 2500 / 4095);
+//! ```
+
+// (millivolts, centimeters) from the datasheet curve, voltage falls with distance
+const DISTANCE_TABLE: [(u32, f32); 8] = [
+    (400, 80.0),
+    (520, 60.0),
+    (750, 40.0),
+    (900, 30.0),
+    (1300, 20.0),
+    (1650, 15.0),
+    (2300, 10.0),
+    (2500, 9.0),
+];
+
+/// Interpolates the distance table, clamped to its ends
+pub fn millivolts_to_cm(mv: u32) -> f32 {
+    if mv <= DISTANCE_TABLE[0].0 {
+        return DISTANCE_TABLE[0].1;
+    }
+    for window in DISTANCE_TABLE.windows(2) {
+        let (mv0, cm0) = window[0];
+        let (mv1, cm1) = window[1];
+        if mv <= mv1 {
+            let t = (mv - mv0) as f32 / (mv1 - mv0) as f32;
+            return cm0 + t * (cm1 - cm0);
+        }
+    }
+    DISTANCE_TABLE[DISTANCE_TABLE.len() - 1].1
+}
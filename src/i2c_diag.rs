@@ -0,0 +1,75 @@
+//! I2C wiring diagnostics
+//!
+//! Crossed SDA and SCL wires look exactly like a dead bus: nothing ACKs.
+//! `check_swapped` scans again with the two pins exchanged in the GPIO
+//! matrix, devices answering only then means the wires are crossed. Used by
+//! the i2c_scan example and by the AHT20 example when the sensor does not
+//! answer.
+
+use esp_hal::{
+    gpio::interconnect::PeripheralOutput,
+    i2c::master::{Config as I2cConfig, I2c},
+    peripheral::Peripheral,
+    peripherals::I2C0,
+    time::Rate,
+    Blocking,
+};
+use esp_println::println;
+
+/// First address outside the reserved range
+pub const FIRST_ADDR: u8 = 0x08;
+/// Last address outside the reserved range
+pub const LAST_ADDR: u8 = 0x77;
+
+/// Probes every address with a one byte read and calls `found` for each one
+/// that ACKs, returns how many did
+pub fn scan(i2c: &mut I2c<'_, Blocking>, mut found: impl FnMut(u8)) -> u32 {
+    let mut count = 0;
+    for addr in FIRST_ADDR..=LAST_ADDR {
+        let mut byte = [0u8];
+        if i2c.read(addr, &mut byte).is_ok() {
+            found(addr);
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Sets up a 100 kHz bus on `sda`/`scl` and scans it
+pub fn scan_pins<'a>(
+    i2c0: impl Peripheral<P = I2C0> + 'a,
+    sda: impl Peripheral<P = impl PeripheralOutput> + 'a,
+    scl: impl Peripheral<P = impl PeripheralOutput> + 'a,
+    found: impl FnMut(u8),
+) -> u32 {
+    let config = I2cConfig::default().with_frequency(Rate::from_khz(100));
+    let mut i2c = match I2c::new(i2c0, config) {
+        Ok(i2c) => i2c,
+        Err(e) => {
+            panic!("Failed to initialize I2C: {:?}", e);
+        }
+    }
+    .with_sda(sda)
+    .with_scl(scl);
+    scan(&mut i2c, found)
+}
+
+/// Scans with `sda` and `scl` exchanged and prints whether that makes
+/// devices appear. Meant for a bus where nothing answered, returns true if
+/// the wires look crossed.
+pub fn check_swapped<'a>(
+    i2c0: impl Peripheral<P = I2C0> + 'a,
+    sda: impl Peripheral<P = impl PeripheralOutput> + 'a,
+    scl: impl Peripheral<P = impl PeripheralOutput> + 'a,
+) -> bool {
+    let swapped = scan_pins(i2c0, scl, sda, |_| {});
+    if swapped > 0 {
+        println!(
+            "Hint: SDA and SCL appear to be swapped, {} device(s) answer with the two pins exchanged",
+            swapped
+        );
+    } else {
+        println!("Nothing answers either way, check power, ground and pull-ups");
+    }
+    swapped > 0
+}
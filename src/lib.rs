@@ -0,0 +1,74 @@
+//! Helpers shared by the firmware and the examples
+
+#![no_std]
+
+use esp_hal::time::{Duration, Instant};
+
+pub mod adc;
+pub mod aht20;
+pub mod bh1750;
+pub mod board;
+pub mod color;
+pub mod config;
+pub mod flash;
+pub mod gp2y0a21;
+pub mod i2c_diag;
+pub mod reconnect;
+#[cfg(feature = "safe-shutdown")]
+pub mod safe_shutdown;
+pub mod sk6812;
+
+#[doc(hidden)]
+pub use esp_println;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Set by build.rs, `unknown` outside of a git checkout
+pub const GIT_HASH: &str = env!("GIT_HASH");
+/// Set by build.rs, UTC
+pub const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
+
+/// Wall-clock deadline for polling loops, so they time out after a real
+/// duration instead of a number of iterations that depends on loop speed:
+///
+/// ```ignore
+/// let timeout = Timeout::from_millis(100);
+/// while sensor_busy() {
+///     if timeout.expired() {
+///         return Err(Error::Timeout);
+///     }
+/// }
+/// ```
+pub struct Timeout {
+    deadline: Instant,
+}
+
+impl Timeout {
+    pub fn new(duration: Duration) -> Self {
+        Timeout {
+            deadline: Instant::now() + duration,
+        }
+    }
+
+    pub fn from_millis(ms: u64) -> Self {
+        Timeout::new(Duration::from_millis(ms))
+    }
+
+    pub fn expired(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+}
+
+/// Prints which firmware is running: the binary (example) name, crate
+/// version, git hash and build time. Call it right after `esp_hal::init`.
+#[macro_export]
+macro_rules! print_banner {
+    () => {
+        $crate::esp_println::println!(
+            "{} v{} ({}, built {} UTC)",
+            env!("CARGO_CRATE_NAME"),
+            $crate::VERSION,
+            $crate::GIT_HASH,
+            $crate::BUILD_TIMESTAMP
+        )
+    };
+}
@@ -0,0 +1,60 @@
+//! Exponential backoff for reconnecting after a lost connection
+//!
+//! Retrying at a fixed short interval hammers an access point that is
+//! rebooting and, with many nodes, delays its recovery further. Retrying
+//! at a fixed long interval leaves a node offline for longer than needed
+//! after a short glitch. `ReconnectPolicy` starts short and doubles the
+//! wait after every failed attempt up to a maximum:
+//!
+//! ```ignore
+//! let mut policy = ReconnectPolicy::new(Duration::from_secs(1), Duration::from_secs(60));
+//! loop {
+//!     if connect().is_ok() {
+//!         policy.reset();
+//!         run_until_disconnected();
+//!     } else {
+//!         sleep(policy.next_delay());
+//!     }
+//! }
+//! ```
+
+use esp_hal::time::Duration;
+
+pub struct ReconnectPolicy {
+    initial: Duration,
+    max: Duration,
+    next: Duration,
+    attempts: u32,
+}
+
+impl ReconnectPolicy {
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        ReconnectPolicy {
+            initial,
+            max,
+            next: initial,
+            attempts: 0,
+        }
+    }
+
+    /// How long to wait before the next attempt: `initial`, then twice as
+    /// long after every call, capped at `max`
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = Duration::from_millis((delay.as_millis() * 2).min(self.max.as_millis()));
+        self.attempts += 1;
+        delay
+    }
+
+    /// Attempts since the last `reset`
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Call after a successful connect, the next outage starts from
+    /// `initial` again
+    pub fn reset(&mut self) {
+        self.next = self.initial;
+        self.attempts = 0;
+    }
+}
@@ -0,0 +1,63 @@
+//! Drives outputs to a safe level when the firmware panics
+//!
+//! Needs the `safe-shutdown` feature, which makes esp-backtrace call
+//! `custom_pre_backtrace` (defined here) first thing in the panic handler.
+//! During setup, register every output that must not stay on after a crash
+//! together with its de-energized level:
+//!
+//! ```ignore
+//! let mut relay = Output::new(peripherals.GPIO3, Level::Low, OutputConfig::default());
+//! safe_shutdown::register(3, Level::Low);
+//! ```
+//!
+//! A panic then runs `safe_shutdown`, which can also be called directly,
+//! e.g. when a fault is detected without panicking.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use esp_hal::gpio::{AnyPin, Level, Output, OutputConfig};
+use esp_println::println;
+
+use crate::board::NUM_GPIOS;
+
+static SAFE_LEVELS: Mutex<RefCell<[Option<Level>; NUM_GPIOS]>> =
+    Mutex::new(RefCell::new([None; NUM_GPIOS]));
+
+/// Makes `safe_shutdown` drive `gpio` to `level`
+pub fn register(gpio: u8, level: Level) {
+    critical_section::with(|cs| {
+        let mut levels = SAFE_LEVELS.borrow_ref_mut(cs);
+        let Some(slot) = levels.get_mut(gpio as usize) else {
+            panic!("GPIO{} does not exist", gpio);
+        };
+        *slot = Some(level);
+    });
+}
+
+/// Drives every registered output to its safe level. Takes the pins by
+/// force, so it works from the panic handler where the drivers owning them
+/// are out of reach.
+pub fn safe_shutdown() {
+    let levels = critical_section::with(|cs| *SAFE_LEVELS.borrow_ref(cs));
+    for (gpio, level) in levels.iter().enumerate() {
+        let Some(level) = *level else {
+            continue;
+        };
+        let output = Output::new(
+            unsafe { AnyPin::steal(gpio as u8) },
+            level,
+            OutputConfig::default(),
+        );
+        // Dropping an `Output` resets its pin to an unconfigured input,
+        // which would leave e.g. a relay driver's input floating. Forgetting
+        // it keeps the pin driven at the safe level until the chip resets.
+        core::mem::forget(output);
+    }
+    println!("Outputs in safe state");
+}
+
+#[no_mangle]
+fn custom_pre_backtrace() {
+    safe_shutdown();
+}
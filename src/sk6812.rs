@@ -0,0 +1,102 @@
+//! sk6812 RGBW LED strip encoding for the RMT peripheral
+//!
+//! A pixel is 32 bits sent as green, red, blue, white, MSB first. Each bit
+//! is one RMT pulse code: a long high pulse for a 1, a short one for a 0.
+//! The timings are in ticks of an RMT channel running at 80 MHz with
+//! `clk_divider(1)`, as set up in the examples:
+//!
+//! ```ignore
+//! let rmt = Rmt::new(peripherals.RMT, Rate::from_mhz(80)).unwrap().into_async();
+//! let mut channel = rmt
+//!     .channel0
+//!     .configure(peripherals.GPIO4, TxChannelConfig::default().with_clk_divider(1))
+//!     .unwrap();
+//! let mut strip = sk6812::Strip::new(channel);
+//! strip.flush(&[(255, 0, 0, 0); 8]).await;
+//! ```
+//!
+//! The strip latches a frame only after the data line stays low for the
+//! reset time (>= 80us). `Strip::flush` waits for that gap if it is called
+//! again too early, otherwise two frames merge into one and the pixels show
+//! shifted or garbled colors.
+
+use embassy_time::{Duration, Instant, Timer};
+use esp_hal::{
+    gpio::Level,
+    rmt::{PulseCode, TxChannelAsync},
+};
+
+const T0H: u16 = 40;
+const T0L: u16 = 85;
+const T1H: u16 = 80;
+const T1L: u16 = 45;
+
+/// Low time on the data line after which the strip latches a frame
+pub const RESET_TIME: Duration = Duration::from_micros(80);
+
+/// Pulse codes per pixel: 32 data bits and the end marker
+pub const PIXEL_CODES: usize = 33;
+
+/// Dims a (r, g, b, w) pixel, `brightness` 255 leaves it unchanged
+pub fn scale(pixel: (u8, u8, u8, u8), brightness: u8) -> (u8, u8, u8, u8) {
+    let scale = |value: u8| (value as u16 * brightness as u16 / 255) as u8;
+    let (r, g, b, w) = pixel;
+    (scale(r), scale(g), scale(b), scale(w))
+}
+
+/// Pulse codes for one pixel, ready for `transmit`
+pub fn encode(r: u8, g: u8, b: u8, w: u8) -> [u32; PIXEL_CODES] {
+    let mut data = [PulseCode::empty(); PIXEL_CODES];
+    let bytes = [g, r, b, w];
+
+    let mut idx = 0;
+    for byte in bytes {
+        for bit in (0..8).rev() {
+            data[idx] = if (byte & (1 << bit)) != 0 {
+                PulseCode::new(Level::High, T1H, Level::Low, T1L)
+            } else {
+                PulseCode::new(Level::High, T0H, Level::Low, T0L)
+            };
+            idx += 1;
+        }
+    }
+    data[32] = PulseCode::new(Level::Low, 800, Level::Low, 0);
+    data
+}
+
+/// Sends one (r, g, b, w) tuple per LED, starting at the first LED of the
+/// strip
+pub async fn write_frame<C: TxChannelAsync>(channel: &mut C, pixels: &[(u8, u8, u8, u8)]) {
+    for &(r, g, b, w) in pixels {
+        let data = encode(r, g, b, w);
+        channel.transmit(&data).await.unwrap();
+    }
+}
+
+/// One strip on one RMT channel, generic so each channel gets its own type
+pub struct Strip<C> {
+    channel: C,
+    last_flush: Instant,
+}
+
+impl<C: TxChannelAsync> Strip<C> {
+    pub fn new(channel: C) -> Self {
+        Strip {
+            channel,
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Sends one frame, first waiting out whatever is left of the reset time
+    /// after the previous frame so both latch separately.
+    pub async fn flush(&mut self, pixels: &[(u8, u8, u8, u8)]) {
+        let since_last = Instant::now() - self.last_flush;
+        if since_last < RESET_TIME {
+            Timer::after(RESET_TIME - since_last).await;
+        }
+
+        write_frame(&mut self.channel, pixels).await;
+
+        self.last_flush = Instant::now();
+    }
+}